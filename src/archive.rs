@@ -0,0 +1,303 @@
+use std::{
+    fs::{create_dir_all, read, read_dir, remove_dir_all, write, File},
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use tar::EntryType;
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    args::Args,
+    config::Config,
+    err::{
+        archive_err::ArchiveErr, args_err::ArgsErr, error::Error,
+        template_err::TemplateErr,
+    },
+    format::Format,
+    prompt::replace_prompt,
+    source::SOURCE_FILE,
+    template::Template,
+};
+
+/// Format of the template archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Extensions of the archive formats
+    const EXTS: [(&'static str, ArchiveFormat); 3] = [
+        (".tar.gz", ArchiveFormat::TarGz),
+        (".tgz", ArchiveFormat::TarGz),
+        (".zip", ArchiveFormat::Zip),
+    ];
+
+    /// Gets archive format based on the file extension
+    pub fn from_path(path: &Path) -> Result<Self, ArchiveErr> {
+        ArchiveFormat::split_ext(path)
+            .map(|(_, format)| format)
+            .ok_or(ArchiveErr::UnknownFormat(
+                path.to_string_lossy().to_string(),
+            ))
+    }
+
+    /// Splits file name of the archive to the name without extension and
+    /// the archive format
+    fn split_ext(path: &Path) -> Option<(String, ArchiveFormat)> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        ArchiveFormat::EXTS.iter().find_map(|(ext, format)| {
+            let stem = name.strip_suffix(ext)?;
+            Some((stem.to_string(), *format))
+        })
+    }
+}
+
+/// Archive entry with path relative to the template directory
+type Entry = (PathBuf, Vec<u8>);
+
+/// Exports template given in args to the archive in `file`
+pub fn export(config: &Config, args: &Args, file: &Path) -> Result<(), Error> {
+    let name = args.template.as_ref().ok_or(ArgsErr::NoTemplate)?;
+    let Some(dir) = config.find_template(name) else {
+        return Err(TemplateErr::NotFound(name.to_string()).into());
+    };
+
+    let format = ArchiveFormat::from_path(file)?;
+    let mut files = dir_files(&dir)?;
+    files.sort();
+
+    match format {
+        ArchiveFormat::TarGz => export_tar(&dir, &files, file),
+        ArchiveFormat::Zip => export_zip(&dir, &files, file),
+    }
+}
+
+/// Imports template from the archive in `file` to the template directory
+/// from the config. Template is named by args or by the archive file name
+pub fn import(config: &Config, args: &Args, file: &Path) -> Result<(), Error> {
+    let format = ArchiveFormat::from_path(file)?;
+    let entries = match format {
+        ArchiveFormat::TarGz => read_tar(file)?,
+        ArchiveFormat::Zip => read_zip(file)?,
+    };
+    let (root, mut entries) = strip_root(entries);
+    check_manifest(&entries)?;
+    entries.retain(|(path, _)| path != Path::new(SOURCE_FILE));
+
+    let name = args
+        .template
+        .clone()
+        .or(root)
+        .or_else(|| ArchiveFormat::split_ext(file).map(|(name, _)| name))
+        .filter(|name| !name.is_empty())
+        .ok_or(ArgsErr::NoTemplate)?;
+    Template::check_name(&name)?;
+
+    let dst = config.template_dir.join(&name);
+    if dst.exists() {
+        if !replace_prompt(&name, args.yes) {
+            return Ok(());
+        }
+        remove_dir_all(&dst)?;
+    }
+
+    create_dir_all(dst.join("template"))?;
+    for (path, content) in entries {
+        let path = dst.join(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, content)?;
+    }
+    Ok(())
+}
+
+/// Gets all files in the directory recursively, paths are relative to the
+/// directory
+pub fn dir_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut res = vec![];
+    walk_dir(dir, &mut |rel, path| {
+        if !path.is_dir() {
+            res.push(rel.to_path_buf());
+        }
+    })?;
+    Ok(res)
+}
+
+/// Calls `f` for all files and directories in the directory recursively
+/// with their path relative to the directory and their full path.
+/// Directories are visited before their contents
+pub fn walk_dir<F>(dir: &Path, f: &mut F) -> Result<(), Error>
+where
+    F: FnMut(&Path, &Path),
+{
+    walk(dir, dir, f)
+}
+
+/// Visits entries in the `dir` recursively, paths are relative to `root`
+fn walk<F>(root: &Path, dir: &Path, f: &mut F) -> Result<(), Error>
+where
+    F: FnMut(&Path, &Path),
+{
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if let Ok(rel) = path.strip_prefix(root) {
+            f(rel, &path);
+        }
+        if path.is_dir() {
+            walk(root, &path, f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes given files from `dir` to tar.gz archive
+fn export_tar(
+    dir: &Path,
+    files: &[PathBuf],
+    file: &Path,
+) -> Result<(), Error> {
+    let encoder = GzEncoder::new(File::create(file)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for rel in files {
+        builder.append_path_with_name(dir.join(rel), rel)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Writes given files from `dir` to zip archive
+fn export_zip(
+    dir: &Path,
+    files: &[PathBuf],
+    file: &Path,
+) -> Result<(), Error> {
+    let mut zip = ZipWriter::new(File::create(file)?);
+    for rel in files {
+        let name: Vec<_> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        zip.start_file(name.join("/"), SimpleFileOptions::default())
+            .map_err(ArchiveErr::from)?;
+        zip.write_all(&read(dir.join(rel))?)?;
+    }
+    zip.finish().map_err(ArchiveErr::from)?;
+    Ok(())
+}
+
+/// Reads files from tar.gz archive
+fn read_tar(file: &Path) -> Result<Vec<Entry>, Error> {
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(file)?));
+    let mut res = vec![];
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if matches!(kind, EntryType::XGlobalHeader | EntryType::XHeader) {
+            continue;
+        }
+
+        let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+        let path = safe_path(&name)?;
+        match kind {
+            EntryType::Directory => continue,
+            EntryType::Regular | EntryType::Continuous
+                if !path.as_os_str().is_empty() => {}
+            _ => return Err(ArchiveErr::UnsupportedEntry(name).into()),
+        }
+
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        res.push((path, content));
+    }
+    Ok(res)
+}
+
+/// Reads files from zip archive
+fn read_zip(file: &Path) -> Result<Vec<Entry>, Error> {
+    let mut archive =
+        ZipArchive::new(File::open(file)?).map_err(ArchiveErr::from)?;
+    let mut res = vec![];
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(ArchiveErr::from)?;
+        let name = entry.name().to_string();
+        let path = safe_path(&name)?;
+        if entry.is_dir() {
+            continue;
+        }
+        if !entry.is_file() || path.as_os_str().is_empty() {
+            return Err(ArchiveErr::UnsupportedEntry(name).into());
+        }
+
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        res.push((path, content));
+    }
+    Ok(res)
+}
+
+/// Converts archive entry name to relative path, refuses absolute paths and
+/// paths containing `..`
+pub fn safe_path(name: &str) -> Result<PathBuf, ArchiveErr> {
+    let mut res = PathBuf::new();
+    for comp in Path::new(name).components() {
+        match comp {
+            Component::Normal(c) => res.push(c),
+            Component::CurDir => {}
+            _ => return Err(ArchiveErr::UnsafePath(name.to_string())),
+        }
+    }
+    Ok(res)
+}
+
+/// Strips directory containing the whole template (such as when directory
+/// of the template was archived instead of its contents). Returns name of
+/// the stripped directory and the entries
+fn strip_root(entries: Vec<Entry>) -> (Option<String>, Vec<Entry>) {
+    let has_manifest = |entries: &[Entry], root: &Path| {
+        Format::ALL.iter().any(|f| {
+            entries.iter().any(|(p, _)| p == &root.join(f.manifest()))
+        })
+    };
+    if has_manifest(&entries, Path::new("")) {
+        return (None, entries);
+    }
+
+    let Some(Component::Normal(root)) =
+        entries.first().and_then(|(p, _)| p.components().next())
+    else {
+        return (None, entries);
+    };
+    let root = PathBuf::from(root);
+    if !has_manifest(&entries, &root)
+        || !entries.iter().all(|(p, _)| p.starts_with(&root))
+    {
+        return (None, entries);
+    }
+
+    let entries = entries
+        .into_iter()
+        .filter_map(|(p, c)| Some((p.strip_prefix(&root).ok()?.into(), c)))
+        .collect();
+    (Some(root.to_string_lossy().to_string()), entries)
+}
+
+/// Checks whether entries contain valid template manifest
+fn check_manifest(entries: &[Entry]) -> Result<(), ArchiveErr> {
+    let manifest = Format::ALL.iter().find_map(|f| {
+        let (_, content) =
+            entries.iter().find(|(p, _)| p == Path::new(f.manifest()))?;
+        Some((*f, content))
+    });
+    let Some((format, content)) = manifest else {
+        return Err(ArchiveErr::NoManifest);
+    };
+
+    Template::parse_manifest(format, &String::from_utf8_lossy(content))
+        .map_err(|e| ArchiveErr::InvalidManifest(Box::new(e)))?;
+    Ok(())
+}
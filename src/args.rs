@@ -6,27 +6,57 @@ use termint::{
     widgets::{grad::Grad, span::StrSpanExtension},
 };
 
-use crate::err::args_err::ArgsErr;
+use crate::{
+    builtins::BUILTINS, err::args_err::ArgsErr, format::Format,
+    parse::ast::Value,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Action {
     Create,
     Remove,
+    Rename,
+    Copy,
     List,
+    ShowVars,
+    SetGlobal,
+    UnsetGlobal,
+    ListGlobal,
+    ShowConfig,
+    InstallBuiltin,
+    Export,
+    Import,
+    Add,
+    Upgrade,
     Help,
     Version,
 }
 
 /// Struct for parsing arguments
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Args {
     pub template: Option<String>,
+    /// Templates loaded after the template (when loading multiple ones)
+    pub extra_templates: Vec<String>,
+    /// Positional arguments of the generator (`template:generator`)
+    pub gen_args: Vec<String>,
     pub dst: Option<String>,
     pub action: Option<Action>,
-    pub vars: HashMap<String, String>,
+    pub vars: HashMap<String, Value>,
+    pub vars_file: Option<String>,
+    pub config: Option<String>,
+    pub format: Option<Format>,
+    pub archive: Option<String>,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub string_vars: bool,
     pub pre: Option<String>,
     pub post: Option<String>,
     pub yes: bool,
+    pub force: bool,
+    pub update: bool,
+    pub no_scripts: bool,
+    pub trust_scripts: bool,
 }
 
 impl Args {
@@ -34,13 +64,45 @@ impl Args {
     pub fn parse(args: std::env::Args) -> Result<Args, ArgsErr> {
         let mut parsed = Args::default();
 
+        let mut defs = vec![];
         let mut args_iter = args.into_iter();
         args_iter.next();
         while let Some(arg) = args_iter.next() {
             match arg.as_str() {
                 "-c" | "--create" => parsed.set_action(Action::Create)?,
                 "-r" | "--remove" => parsed.set_action(Action::Remove)?,
+                "--rename" => {
+                    parsed.set_action(Action::Rename)?;
+                    parsed.set_src_target(&mut args_iter)?
+                }
+                "--copy" => {
+                    parsed.set_action(Action::Copy)?;
+                    parsed.set_src_target(&mut args_iter)?
+                }
                 "-l" | "--list" => parsed.set_action(Action::List)?,
+                "--show-vars" => parsed.set_action(Action::ShowVars)?,
+                "--set-global" => parsed.set_action(Action::SetGlobal)?,
+                "--unset-global" => parsed.set_action(Action::UnsetGlobal)?,
+                "--list-global" => parsed.set_action(Action::ListGlobal)?,
+                "--show-config" => parsed.set_action(Action::ShowConfig)?,
+                "--export" => {
+                    parsed.set_action(Action::Export)?;
+                    parsed.set_src_target(&mut args_iter)?
+                }
+                "--import" => {
+                    parsed.set_action(Action::Import)?;
+                    parsed.archive =
+                        Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
+                }
+                "--add" => {
+                    parsed.set_action(Action::Add)?;
+                    parsed.source =
+                        Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
+                }
+                "--upgrade" => parsed.set_action(Action::Upgrade)?,
+                "--install-builtin" => {
+                    parsed.set_action(Action::InstallBuiltin)?
+                }
                 "-h" | "--help" => parsed.set_action(Action::Help)?,
                 "-v" | "--version" => parsed.set_action(Action::Version)?,
                 "-d" | "--dir" => parsed.set_path(
@@ -50,18 +112,61 @@ impl Args {
                     parsed.pre =
                         Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
                 }
+                "--vars-file" => {
+                    parsed.vars_file =
+                        Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
+                }
+                "--format" => {
+                    parsed.format = Some(
+                        args_iter
+                            .next()
+                            .ok_or(ArgsErr::MissingParam)?
+                            .parse()?,
+                    )
+                }
+                "--config" => {
+                    parsed.config =
+                        Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
+                }
                 "--post" => {
                     parsed.post =
                         Some(args_iter.next().ok_or(ArgsErr::MissingParam)?)
                 }
                 "-y" | "--yes" => parsed.yes = true,
-                var if var.starts_with("-D") => parsed.parse_var(var),
-                name => parsed.set_template(name.to_string())?,
+                "--force" => parsed.force = true,
+                "--update" => parsed.update = true,
+                "--no-scripts" => parsed.no_scripts = true,
+                "--trust-scripts" => parsed.trust_scripts = true,
+                "--string-vars" => parsed.string_vars = true,
+                var if var.starts_with("-D") => defs.push(arg),
+                name => parsed.add_template(name.to_string()),
             }
         }
+
+        for def in defs.iter() {
+            parsed.parse_var(def)?;
+        }
+        if parsed.generator().is_some() && parsed.action.is_none() {
+            parsed.gen_args = std::mem::take(&mut parsed.extra_templates);
+        }
+        let multiple = matches!(parsed.action, None | Some(Action::ShowVars));
+        if !parsed.extra_templates.is_empty() && !multiple {
+            return Err(ArgsErr::MultipleTemplates);
+        }
         Ok(parsed)
     }
 
+    /// Gets template and generator name, when generator is given as
+    /// `template:generator`
+    pub fn generator(&self) -> Option<(&str, &str)> {
+        self.template.as_ref()?.split_once(':')
+    }
+
+    /// Gets names of all the templates given in args
+    pub fn templates(&self) -> impl Iterator<Item = &String> {
+        self.template.iter().chain(self.extra_templates.iter())
+    }
+
     /// Checks if template name is provided
     pub fn check_template(&self) -> Result<(), ArgsErr> {
         if self.template.is_none() {
@@ -70,17 +175,10 @@ impl Args {
         Ok(())
     }
 
-    /// Adds variable if isn't already defined
-    pub fn add_var(&mut self, name: &str, value: String) {
-        if !self.vars.contains_key(name) {
-            self.vars.insert(name.to_string(), value);
-        }
-    }
-
     /// Gets destination path
     pub fn get_path(&self) -> PathBuf {
         if let Some(dst) = &self.dst {
-            canonicalize(&dst).unwrap_or(PathBuf::from(dst))
+            canonicalize(dst).unwrap_or(PathBuf::from(dst))
         } else {
             canonicalize(".").unwrap_or(PathBuf::from("."))
         }
@@ -96,22 +194,98 @@ impl Args {
         help!(
             "Usage":
             "makeit" ["template name"] ["options"] => "Loads given template\n"
+            "makeit" ["template name"] ["template name"] ["..."] =>
+                "Loads given templates in order into one project\n"
+            "makeit" ["template:generator"] ["arguments"] ["options"] =>
+                "Runs generator of the template in the current project\n"
             "makeit" ["options"] => "Behaves according to the options\n"
             "Options":
             "-c  --create" => "Creates new template with given name\n"
             "-l  --list" => "Lists all templates\n"
             "-r  --remove" => "Remove template with given name\n"
+            "--rename" ["old"] ["new"] => "Renames template\n"
+            "--copy" ["src"] ["dst"] =>
+                "Copies template to the template directory\n"
+            "--show-vars" =>
+                "Prints variables of the template and where they come from\n"
+            "--set-global" =>
+                "Sets variables defined with '-D' as global variables\n"
+            "--unset-global" =>
+                "Removes global variables with names defined with '-D'\n"
+            "--list-global" => "Lists all global variables\n"
+            "--show-config" =>
+                "Prints the config file path and the effective config\n"
+            "--export" ["template"] ["file"] =>
+                "Exports template to tar.gz or zip archive\n"
+            "--import" ["file"] => {
+                "Imports template from tar.gz or zip archive, template is",
+                "named by the archive file name, when no name is given\n"
+            }
+            "--add" ["source"] => {
+                "Adds template from directory or git repository, template",
+                "is named by the source name, when no name is given\n"
+            }
+            "--upgrade" => {
+                "Upgrades added template (or all added templates) from",
+                "its source\n"
+            }
+            "--install-builtin" => {
+                "Installs bundled templates (or only the given one), reports",
+                "installed templates, which differ from the bundled ones\n"
+            }
+            "--update" => {
+                "Updates only template files with '--create', template",
+                "manifest is kept\n"
+            }
+            "--force" =>
+                "Overwrites templates with '--install-builtin'\n"
+            "--config" ["path"] =>
+                "Uses given config file (overrides 'MAKEIT_CONFIG')\n"
             "-d  --dir" ["path"] =>
                 "Sets directory to create/load template from/to\n"
             "--pre" ["script"] =>
                 "Sets pre-script to given script (only with '--create')\n"
             "--post" ["script"] =>
                 "Sets post-script to given script (only with '--create')\n"
-            "-D\x1b[39m[variable name]=[value]" => "Defines a variable\n"
-            "-y  --yes" => "Automatically answers yes in yes-no prompts\n"
+            "--format" ["format"] => {
+                "Sets format of the template manifest (json, toml or yaml),",
+                "only with '--create' (default is json)\n"
+            }
+            "-D\x1b[39m[variable name]=[value]" =>
+                "Defines a variable, its type is inferred from the value\n"
+            "-D\x1b[39m[variable name]:[type]=[value]" =>
+                "Defines typed variable (str, bool, num, list or json)\n"
+            "--string-vars" =>
+                "Converts all variables to strings (compatibility mode)\n"
+            "--vars-file" ["path"] =>
+                "Loads variables from JSON, TOML or YAML file\n"
+            "--no-scripts" =>
+                "Loads template without running its pre and post hooks\n"
+            "--trust-scripts" => {
+                "Runs hooks of untrusted templates without asking",
+                "(required for them with '--yes')\n"
+            }
+            "-y  --yes" => {
+                "Automatically answers yes in yes-no prompts, except for",
+                "untrusted hooks (see '--trust-scripts')\n"
+            }
             "-v  --version" => "Prints the version number"
             "-h   --help" => "Prints this help (other options are ignored)"
         );
+        println!("{}:", "Internal variables".fg(Fg::Green));
+        for (name, desc) in BUILTINS {
+            println!("  {}", name.fg(Fg::Yellow));
+            println!("    {desc}");
+        }
+    }
+
+    /// Adds template, templates after the first one are extra templates
+    fn add_template(&mut self, template: String) {
+        if self.template.is_some() {
+            self.extra_templates.push(template);
+        } else {
+            self.template = Some(template);
+        }
     }
 
     fn set_template(&mut self, template: String) -> Result<(), ArgsErr> {
@@ -123,6 +297,16 @@ impl Args {
         }
     }
 
+    /// Sets template and target (new name or file) to the next two arguments
+    fn set_src_target(
+        &mut self,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<(), ArgsErr> {
+        self.set_template(args.next().ok_or(ArgsErr::MissingParam)?)?;
+        self.target = Some(args.next().ok_or(ArgsErr::MissingParam)?);
+        Ok(())
+    }
+
     /// Sets action to given value, returns Err when already set
     fn set_action(&mut self, action: Action) -> Result<(), ArgsErr> {
         if self.action.is_some() {
@@ -143,27 +327,49 @@ impl Args {
         }
     }
 
-    /// Parses variable
-    fn parse_var(&mut self, arg: &str) {
+    /// Parses variable, its type can be given after its name. Otherwise
+    /// the type is inferred from the value and variable without value is
+    /// `true`, unless string variables are enabled
+    fn parse_var(&mut self, arg: &str) -> Result<(), ArgsErr> {
         let var = &arg[2..];
-        if let Some((name, val)) = var.split_once('=') {
-            self.vars.insert(name.to_string(), val.to_string());
-        } else {
-            self.vars.insert(var.to_string(), "".to_string());
-        }
+        let (def, val) = match var.split_once('=') {
+            Some((def, val)) => (def, Some(val)),
+            None => (var, None),
+        };
+        let (name, val) = match def.split_once(':') {
+            Some((name, kind)) => {
+                (name, Args::parse_typed(name, kind, val.unwrap_or(""))?)
+            }
+            None if self.string_vars => {
+                (def, Value::String(val.unwrap_or("").to_string()))
+            }
+            None => (def, val.map(Value::infer).unwrap_or(Value::Bool(true))),
+        };
+        self.vars.insert(name.to_string(), val);
+        Ok(())
     }
-}
 
-impl Default for Args {
-    fn default() -> Self {
-        Self {
-            template: None,
-            dst: None,
-            action: None,
-            yes: false,
-            pre: None,
-            post: None,
-            vars: HashMap::new(),
+    /// Parses value of the variable with given type
+    fn parse_typed(
+        name: &str,
+        kind: &str,
+        val: &str,
+    ) -> Result<Value, ArgsErr> {
+        let invalid = || ArgsErr::InvalidVarValue(name.to_string());
+        match kind {
+            "str" => Ok(Value::String(val.to_string())),
+            "bool" => match val {
+                "" | "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                _ => Err(invalid()),
+            },
+            "num" => val.parse().map(Value::Number).map_err(|_| invalid()),
+            "list" if val.is_empty() => Ok(Value::List(vec![])),
+            "list" => {
+                Ok(Value::List(val.split(',').map(Value::from).collect()))
+            }
+            "json" => serde_json::from_str(val).map_err(|_| invalid()),
+            _ => Err(ArgsErr::InvalidVarType(kind.to_string())),
         }
     }
 }
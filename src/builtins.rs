@@ -0,0 +1,209 @@
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    env,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    Datelike, Local,
+};
+
+use crate::{err::error::Error, parse::ast::Value};
+
+/// Default format of the `_DATE` variable
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Names of the internal variables with their description
+pub const BUILTINS: [(&str, &str); 12] = [
+    ("_PNAME", "Project name based on project directory"),
+    ("_PDIR", "Project directory"),
+    ("_OS", "Operating system"),
+    ("_ARCH", "CPU architecture"),
+    ("_YEAR", "Current year"),
+    ("_DATE", "Current date (format can be set in the config)"),
+    ("_USER", "Name of the current user"),
+    ("_HOME", "Home directory of the current user"),
+    ("_TEMPLATE", "Name of the loaded template"),
+    ("_MAKEIT_VERSION", "Version of makeit"),
+    ("_GIT_NAME", "The 'user.name' from the git config"),
+    ("_GIT_EMAIL", "The 'user.email' from the git config"),
+];
+
+/// Computes internal variables. Each variable is computed only when it's
+/// first requested and git config is read at most once
+#[derive(Debug)]
+pub struct Builtins {
+    dst: PathBuf,
+    template: Option<String>,
+    date_format: String,
+    git: OnceCell<GitUser>,
+    values: [OnceCell<Option<Value>>; BUILTINS.len()],
+}
+
+/// User identity from the git config
+#[derive(Debug, Default)]
+struct GitUser {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Builtins {
+    /// Creates new built-ins for project in `dst` directory. Returns error
+    /// when the date format is invalid
+    pub fn new(
+        dst: &Path,
+        template: Option<String>,
+        date_format: Option<&str>,
+    ) -> Result<Self, Error> {
+        let date_format = date_format.unwrap_or(DATE_FORMAT);
+        if StrftimeItems::new(date_format).any(|i| matches!(i, Item::Error)) {
+            return Err(Error::Msg(format!(
+                "invalid date format '{date_format}'"
+            )));
+        }
+
+        Ok(Self {
+            dst: dst.to_path_buf(),
+            template,
+            date_format: date_format.to_string(),
+            git: OnceCell::new(),
+            values: std::array::from_fn(|_| OnceCell::new()),
+        })
+    }
+
+    /// Gets value of the internal variable with given name, it's computed
+    /// on the first call. Returns `None` when the variable doesn't exist or
+    /// its value can't be determined
+    pub fn get(&self, name: &str) -> Option<Value> {
+        let i = BUILTINS.iter().position(|(n, _)| *n == name)?;
+        self.values[i].get_or_init(|| self.compute(name)).clone()
+    }
+
+    /// Checks whether the internal variable with given name was computed
+    /// This exists for testing purposes
+    #[allow(unused)]
+    pub fn is_computed(&self, name: &str) -> bool {
+        BUILTINS
+            .iter()
+            .position(|(n, _)| *n == name)
+            .is_some_and(|i| self.values[i].get().is_some())
+    }
+
+    /// Computes value of the internal variable with given name
+    fn compute(&self, name: &str) -> Option<Value> {
+        match name {
+            "_PNAME" => Some(
+                self.dst.file_name()?.to_string_lossy().to_string().into(),
+            ),
+            "_PDIR" => Some(self.dst.to_string_lossy().to_string().into()),
+            "_OS" => Some(env::consts::OS.into()),
+            "_ARCH" => Some(env::consts::ARCH.into()),
+            "_YEAR" => Some(Value::Number(Local::now().year() as f64)),
+            "_DATE" => {
+                Some(Local::now().format(&self.date_format).to_string().into())
+            }
+            "_USER" => env::var("USER")
+                .or(env::var("USERNAME"))
+                .ok()
+                .map(Value::String),
+            "_HOME" => {
+                Some(dirs::home_dir()?.to_string_lossy().to_string().into())
+            }
+            "_TEMPLATE" => self.template.as_deref().map(Value::from),
+            "_MAKEIT_VERSION" => Some(env!("CARGO_PKG_VERSION").into()),
+            "_GIT_NAME" => self.git().name.as_deref().map(Value::from),
+            "_GIT_EMAIL" => self.git().email.as_deref().map(Value::from),
+            _ => None,
+        }
+    }
+
+    /// Gets all the internal variables, which have value
+    pub fn vars(&self) -> HashMap<String, Value> {
+        BUILTINS
+            .iter()
+            .filter_map(|(name, _)| Some((name.to_string(), self.get(name)?)))
+            .collect()
+    }
+
+    /// Gets git user, reads it from the git config on the first call
+    fn git(&self) -> &GitUser {
+        self.git.get_or_init(|| GitUser::load(&self.dst))
+    }
+}
+
+impl GitUser {
+    /// Loads git user from the global git config files, which are overriden
+    /// by the config of the repository containing `dst`
+    fn load(dst: &Path) -> Self {
+        let mut user = GitUser::default();
+
+        let xdg = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")));
+        if let Some(xdg) = xdg {
+            user.read(&xdg.join("git").join("config"));
+        }
+        if let Some(home) = dirs::home_dir() {
+            user.read(&home.join(".gitconfig"));
+        }
+        if let Some(local) = dst
+            .ancestors()
+            .map(|dir| dir.join(".git").join("config"))
+            .find(|path| path.is_file())
+        {
+            user.read(&local);
+        }
+        user
+    }
+
+    /// Reads user from the git config file, missing file is ignored
+    fn read(&mut self, path: &Path) {
+        let Ok(content) = read_to_string(path) else {
+            return;
+        };
+
+        let mut in_user = false;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[') {
+                let section = section.split(']').next().unwrap_or("");
+                in_user = section.trim().eq_ignore_ascii_case("user");
+                continue;
+            }
+            if !in_user {
+                continue;
+            }
+
+            let Some((key, val)) = line.split_once('=') else {
+                continue;
+            };
+            let val = GitUser::parse_value(val);
+            match key.trim().to_ascii_lowercase().as_str() {
+                "name" => self.name = Some(val),
+                "email" => self.email = Some(val),
+                _ => {}
+            }
+        }
+    }
+
+    /// Parses value from the git config, removes quotes and comments
+    fn parse_value(val: &str) -> String {
+        let mut res = String::new();
+        let mut quoted = false;
+        let mut chars = val.trim().chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => quoted = !quoted,
+                '#' | ';' if !quoted => break,
+                '\\' => res.extend(chars.next()),
+                _ => res.push(c),
+            }
+        }
+        res.trim_end().to_string()
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    fs::{create_dir_all, read, remove_dir_all, write},
+    io,
+    path::{Path, PathBuf},
+};
+
+use include_dir::{include_dir, Dir, File};
+use termint::{enums::fg::Fg, widgets::span::StrSpanExtension};
+
+use crate::{
+    archive::dir_files,
+    args::Args,
+    config::Config,
+    err::{error::Error, template_err::TemplateErr},
+};
+
+/// Official templates bundled in the binary
+static TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+/// State of the installed bundled template
+#[derive(Debug, PartialEq, Eq)]
+pub enum InstallState {
+    /// Template isn't installed
+    Missing,
+    /// Installed template is the same as the bundled one
+    UpToDate,
+    /// Installed template differs in given files (relative to the template
+    /// directory)
+    Differs(Vec<PathBuf>),
+}
+
+/// Gets names of all the bundled templates
+pub fn names() -> Vec<&'static str> {
+    TEMPLATES
+        .dirs()
+        .filter_map(|d| d.path().file_name()?.to_str())
+        .collect()
+}
+
+/// Installs bundled templates to the template directory from the config.
+/// Only template given in args is installed, when it's set. Templates that
+/// differ from the bundled ones are overwritten only with `--force`
+pub fn install(config: &Config, args: &Args) -> Result<(), Error> {
+    let names = match &args.template {
+        Some(name) => vec![name.as_str()],
+        None => names(),
+    };
+
+    for name in names {
+        let tmplt = get(name)?;
+        let dst = config.template_dir.join(name);
+
+        match state(config, name)? {
+            InstallState::UpToDate => {
+                println!("{name} {}", "(up to date)".fg(Fg::Gray))
+            }
+            InstallState::Missing => {
+                write_template(tmplt, &dst)?;
+                println!("{name} {}", "(installed)".fg(Fg::Green));
+            }
+            InstallState::Differs(_) if args.force => {
+                remove_dir_all(&dst)?;
+                write_template(tmplt, &dst)?;
+                println!("{name} {}", "(overwritten)".fg(Fg::Green));
+            }
+            InstallState::Differs(files) => {
+                println!(
+                    "{name} {}",
+                    "(differs from the bundled version, use '--force' to \
+                     overwrite it)"
+                        .fg(Fg::Yellow)
+                );
+                for file in files {
+                    println!("  {}", file.to_string_lossy());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks state of the installed bundled template with given name in the
+/// template directory from the config
+pub fn state(config: &Config, name: &str) -> Result<InstallState, Error> {
+    let tmplt = get(name)?;
+    let dst = config.template_dir.join(name);
+    if !dst.exists() {
+        return Ok(InstallState::Missing);
+    }
+
+    let mut bundled = vec![];
+    bundled_files(tmplt, &mut bundled);
+
+    let mut differs = vec![];
+    for file in bundled.iter() {
+        let path = rel_path(tmplt, file);
+        match read(dst.join(&path)) {
+            Ok(content) if content == file.contents() => {}
+            Ok(_) => differs.push(path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                differs.push(path)
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    for path in dir_files(&dst)? {
+        if !bundled.iter().any(|f| rel_path(tmplt, f) == path) {
+            differs.push(path);
+        }
+    }
+
+    if differs.is_empty() {
+        Ok(InstallState::UpToDate)
+    } else {
+        differs.sort();
+        Ok(InstallState::Differs(differs))
+    }
+}
+
+/// Gets bundled template with given name
+fn get(name: &str) -> Result<&'static Dir<'static>, TemplateErr> {
+    TEMPLATES
+        .get_dir(name)
+        .ok_or(TemplateErr::NotFound(name.to_string()))
+}
+
+/// Writes bundled template to `dst` directory. Template files directory is
+/// always created, because empty directories aren't bundled
+fn write_template(tmplt: &Dir, dst: &Path) -> Result<(), Error> {
+    let mut files = vec![];
+    bundled_files(tmplt, &mut files);
+    create_dir_all(dst.join("template"))?;
+
+    for file in files {
+        let path = dst.join(rel_path(tmplt, file));
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, file.contents())?;
+    }
+    Ok(())
+}
+
+/// Collects all files in the bundled directory recursively
+fn bundled_files<'a>(dir: &'a Dir<'a>, res: &mut Vec<&'a File<'a>>) {
+    res.extend(dir.files());
+    for sub in dir.dirs() {
+        bundled_files(sub, res);
+    }
+}
+
+/// Gets path of the bundled file relative to its template directory
+fn rel_path(tmplt: &Dir, file: &File) -> PathBuf {
+    file.path()
+        .strip_prefix(tmplt.path())
+        .unwrap_or(file.path())
+        .to_path_buf()
+}
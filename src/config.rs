@@ -1,45 +1,196 @@
 use std::{
+    collections::HashMap,
+    env,
+    ffi::OsStr,
+    fmt::Display,
     fs::{create_dir_all, read_to_string, File},
-    io::Write,
-    path::PathBuf,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
 };
 
 use serde::{Deserialize, Serialize};
 
+use crate::{err::config_err::ConfigErr, parse::ast::Value};
+
+/// Environment variable containing path to the config file
+pub const CONFIG_ENV: &str = "MAKEIT_CONFIG";
+
+/// Environment variable containing additional template directories
+pub const TEMPLATE_PATH_ENV: &str = "MAKEIT_TEMPLATE_PATH";
+
+/// Project-local templates directory, it's searched for in the current
+/// directory and its ancestors
+pub const PROJECT_TEMPLATES: &str = ".makeit/templates";
+
+/// Represents where the template directory comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Project-local templates directory ([`PROJECT_TEMPLATES`])
+    Project,
+    /// Directory from [`TEMPLATE_PATH_ENV`] environment variable
+    Env,
+    /// Directory from the config
+    Config,
+}
+
+impl Display for TemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateSource::Project => write!(f, "project"),
+            TemplateSource::Env => write!(f, "{TEMPLATE_PATH_ENV}"),
+            TemplateSource::Config => write!(f, "config"),
+        }
+    }
+}
+
+/// Directory containing templates
+#[derive(Debug, Clone)]
+pub struct TemplateDir {
+    pub path: PathBuf,
+    pub source: TemplateSource,
+}
+
+impl Display for TemplateDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.source, self.path.to_string_lossy())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
-    #[serde(alias = "templateDir")]
+    /// Path of the config file
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(rename = "templateDir", alias = "template_dir")]
     pub template_dir: PathBuf,
+    /// Additional template directories, searched after `template_dir`
+    #[serde(
+        default,
+        rename = "templateDirs",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub template_dirs: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vars: HashMap<String, Value>,
+    #[serde(
+        default,
+        rename = "dateFormat",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date_format: Option<String>,
 }
 
 impl Config {
-    /// Loads config
-    pub fn load() -> Result<Self, String> {
-        let mut dir = Config::get_dir();
-        dir.push("config.json");
+    /// Loads config from given path, from path in [`CONFIG_ENV`] or from
+    /// the default location. Default config is used only when there is no
+    /// config in the default location
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigErr> {
+        let explicit = path.map(PathBuf::from).or_else(|| {
+            env::var_os(CONFIG_ENV)
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+        });
+        let file = explicit.clone().unwrap_or_else(Config::default_path);
+
+        let content = match read_to_string(&file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return match explicit {
+                    Some(_) => Err(ConfigErr::NotFound(file)),
+                    None => Ok(Self::default()),
+                };
+            }
+            Err(e) => return Err(ConfigErr::Read(file, e)),
+        };
 
-        match serde_json::from_str::<Self>(
-            &read_to_string(&dir).unwrap_or(String::new()),
-        ) {
-            Ok(conf) => Ok(conf),
-            Err(_) => Ok(Self::default()),
+        let mut config = serde_json::from_str::<Self>(&content)
+            .map_err(|e| ConfigErr::Invalid(file.clone(), e))?;
+        config.path = file;
+        Ok(config)
+    }
+
+    /// Saves config to the file it was loaded from
+    pub fn save(&self) -> Result<(), ConfigErr> {
+        let err = |e| ConfigErr::Write(self.path.clone(), e);
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir).map_err(err)?;
         }
+
+        let json_string = serde_json::to_string_pretty(self)
+            .map_err(|e| ConfigErr::Invalid(self.path.clone(), e))?;
+        let mut file = File::create(&self.path).map_err(err)?;
+        file.write_all(json_string.as_bytes()).map_err(err)
     }
 
-    /// Saves config
-    pub fn _save(&self) -> Result<(), String> {
-        let mut dir = Config::get_dir();
-        create_dir_all(&dir).map_err(|e| e.to_string())?;
+    /// Gets all template directories ordered by their priority: project
+    /// templates, directories from [`TEMPLATE_PATH_ENV`] and directories
+    /// from the config
+    pub fn template_dirs(&self) -> Vec<TemplateDir> {
+        self.template_dirs_in(
+            env::current_dir().ok().as_deref(),
+            env::var_os(TEMPLATE_PATH_ENV).as_deref(),
+        )
+    }
+
+    /// Gets all template directories like [`Config::template_dirs`], with
+    /// project templates searched for from `cwd` and with `paths` used as
+    /// the value of [`TEMPLATE_PATH_ENV`]
+    pub fn template_dirs_in(
+        &self,
+        cwd: Option<&Path>,
+        paths: Option<&OsStr>,
+    ) -> Vec<TemplateDir> {
+        let mut dirs: Vec<TemplateDir> = vec![];
+        let mut add = |path: PathBuf, source| {
+            if !dirs.iter().any(|d| d.path == path) {
+                dirs.push(TemplateDir { path, source });
+            }
+        };
+
+        if let Some(project) = cwd.and_then(Config::project_dir) {
+            add(project, TemplateSource::Project);
+        }
+        if let Some(paths) = paths {
+            env::split_paths(paths)
+                .filter(|p| !p.as_os_str().is_empty())
+                .for_each(|p| add(p, TemplateSource::Env));
+        }
+        add(self.template_dir.clone(), TemplateSource::Config);
+        for dir in self.template_dirs.iter() {
+            add(dir.clone(), TemplateSource::Config);
+        }
+        dirs
+    }
+
+    /// Finds directory of the template with given name in the template
+    /// directories, the one with the highest priority is returned
+    pub fn find_template(&self, name: &str) -> Option<PathBuf> {
+        self.template_dirs()
+            .into_iter()
+            .map(|d| d.path.join(name))
+            .find(|p| p.is_dir())
+    }
 
-        dir.push("config.json");
-        let mut file = File::create(&dir).map_err(|e| e.to_string())?;
+    /// Gets directories with partials shared by all templates, ordered by
+    /// priority of the template directories
+    pub fn partials_dirs(&self) -> Vec<PathBuf> {
+        self.template_dirs()
+            .into_iter()
+            .map(|d| d.path.join("partials"))
+            .collect()
+    }
 
-        let json_string =
-            serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
-        file.write_all(json_string.as_bytes())
-            .map_err(|e| e.to_string())?;
+    /// Finds project-local templates directory in the `cwd` directory or in
+    /// its ancestors
+    fn project_dir(cwd: &Path) -> Option<PathBuf> {
+        cwd.ancestors()
+            .map(|dir| dir.join(PROJECT_TEMPLATES))
+            .find(|dir| dir.is_dir())
+    }
 
-        Ok(())
+    /// Gets path of the config file in the default location
+    fn default_path() -> PathBuf {
+        Config::get_dir().join("config.json")
     }
 
     /// Gets config dir
@@ -54,6 +205,12 @@ impl Default for Config {
     fn default() -> Self {
         let mut dir = Config::get_dir();
         dir.push("templates");
-        Self { template_dir: dir }
+        Self {
+            path: Config::default_path(),
+            template_dir: dir,
+            template_dirs: vec![],
+            vars: HashMap::new(),
+            date_format: None,
+        }
     }
 }
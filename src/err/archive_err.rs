@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+use super::error::Error;
+
+/// Enum representing error when exporting or importing template archive
+#[derive(Debug)]
+pub enum ArchiveErr {
+    UnknownFormat(String),
+    UnsafePath(String),
+    UnsupportedEntry(String),
+    NoManifest,
+    InvalidManifest(Box<Error>),
+    Zip(zip::result::ZipError),
+}
+
+impl Display for ArchiveErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveErr::UnknownFormat(p) => write!(
+                f,
+                "unknown archive format of '{p}' (expected .tar.gz, .tgz or \
+                 .zip)"
+            ),
+            ArchiveErr::UnsafePath(p) => {
+                write!(f, "archive contains unsafe path '{p}'")
+            }
+            ArchiveErr::UnsupportedEntry(p) => {
+                write!(f, "archive entry '{p}' is not a file or directory")
+            }
+            ArchiveErr::NoManifest => {
+                write!(f, "archive doesn't contain template manifest")
+            }
+            ArchiveErr::InvalidManifest(e) => {
+                write!(f, "invalid template manifest in archive: {e}")
+            }
+            ArchiveErr::Zip(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for ArchiveErr {
+    fn from(value: zip::result::ZipError) -> Self {
+        Self::Zip(value)
+    }
+}
@@ -8,6 +8,9 @@ pub enum ArgsErr {
     MultipleActions,
     MultiplePaths,
     MissingParam,
+    InvalidVarType(String),
+    InvalidVarValue(String),
+    InvalidFormat(String),
 }
 
 impl Display for ArgsErr {
@@ -20,6 +23,15 @@ impl Display for ArgsErr {
             ArgsErr::MultipleActions => write!(f, "multiple actions provided"),
             ArgsErr::MultiplePaths => write!(f, "multiple paths provided"),
             ArgsErr::MissingParam => write!(f, "missing argument parameter"),
+            ArgsErr::InvalidVarType(t) => {
+                write!(f, "invalid variable type '{t}'")
+            }
+            ArgsErr::InvalidVarValue(n) => {
+                write!(f, "invalid value of variable '{n}'")
+            }
+            ArgsErr::InvalidFormat(n) => {
+                write!(f, "invalid format '{n}' (expected json, toml or yaml)")
+            }
         }
     }
 }
@@ -0,0 +1,29 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+/// Enum representing error when loading or saving config
+#[derive(Debug)]
+pub enum ConfigErr {
+    NotFound(PathBuf),
+    Read(PathBuf, io::Error),
+    Invalid(PathBuf, serde_json::Error),
+    Write(PathBuf, io::Error),
+}
+
+impl Display for ConfigErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigErr::NotFound(p) => {
+                write!(f, "config file '{}' not found", p.to_string_lossy())
+            }
+            ConfigErr::Read(p, e) => {
+                write!(f, "reading config file '{}': {e}", p.to_string_lossy())
+            }
+            ConfigErr::Invalid(p, e) => {
+                write!(f, "invalid config file '{}': {e}", p.to_string_lossy())
+            }
+            ConfigErr::Write(p, e) => {
+                write!(f, "writing config file '{}': {e}", p.to_string_lossy())
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use std::{fmt::Display, io};
 
 use super::{
-    args_err::ArgsErr, lexer_err::LexerErr, template_err::TemplateErr,
+    archive_err::ArchiveErr, args_err::ArgsErr, config_err::ConfigErr,
+    hook_err::HookErr, include_err::IncludeErr, lexer_err::LexerErr,
+    source_err::SourceErr, template_err::TemplateErr,
 };
 
 /// Generic error type
@@ -9,9 +11,17 @@ use super::{
 pub enum Error {
     IOErr(io::Error),
     ArgsErr(ArgsErr),
+    ArchiveErr(ArchiveErr),
+    ConfigErr(ConfigErr),
     LexerErr(LexerErr),
     TemplateErr(TemplateErr),
+    HookErr(HookErr),
+    IncludeErr(IncludeErr),
+    SourceErr(SourceErr),
     Serde(serde_json::Error),
+    Toml(toml::de::Error),
+    TomlSer(toml::ser::Error),
+    Yaml(serde_yaml::Error),
     Msg(String),
 }
 
@@ -20,9 +30,17 @@ impl Display for Error {
         match self {
             Error::IOErr(e) => write!(f, "{e}"),
             Error::ArgsErr(e) => write!(f, "{e}"),
+            Error::ArchiveErr(e) => write!(f, "{e}"),
+            Error::ConfigErr(e) => write!(f, "{e}"),
             Error::LexerErr(e) => write!(f, "{e}"),
             Error::TemplateErr(e) => write!(f, "{e}"),
+            Error::HookErr(e) => write!(f, "{e}"),
+            Error::IncludeErr(e) => write!(f, "{e}"),
+            Error::SourceErr(e) => write!(f, "{e}"),
             Error::Serde(e) => write!(f, "{e}"),
+            Error::Toml(e) => write!(f, "{e}"),
+            Error::TomlSer(e) => write!(f, "{e}"),
+            Error::Yaml(e) => write!(f, "{e}"),
             Error::Msg(m) => write!(f, "{m}"),
         }
     }
@@ -40,6 +58,18 @@ impl From<ArgsErr> for Error {
     }
 }
 
+impl From<ArchiveErr> for Error {
+    fn from(value: ArchiveErr) -> Self {
+        Self::ArchiveErr(value)
+    }
+}
+
+impl From<ConfigErr> for Error {
+    fn from(value: ConfigErr) -> Self {
+        Self::ConfigErr(value)
+    }
+}
+
 impl From<LexerErr> for Error {
     fn from(value: LexerErr) -> Self {
         Self::LexerErr(value)
@@ -52,12 +82,48 @@ impl From<TemplateErr> for Error {
     }
 }
 
+impl From<HookErr> for Error {
+    fn from(value: HookErr) -> Self {
+        Self::HookErr(value)
+    }
+}
+
+impl From<IncludeErr> for Error {
+    fn from(value: IncludeErr) -> Self {
+        Self::IncludeErr(value)
+    }
+}
+
+impl From<SourceErr> for Error {
+    fn from(value: SourceErr) -> Self {
+        Self::SourceErr(value)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(value: serde_json::Error) -> Self {
         Self::Serde(value)
     }
 }
 
+impl From<toml::de::Error> for Error {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+impl From<toml::ser::Error> for Error {
+    fn from(value: toml::ser::Error) -> Self {
+        Self::TomlSer(value)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(value: serde_yaml::Error) -> Self {
+        Self::Yaml(value)
+    }
+}
+
 impl From<String> for Error {
     fn from(value: String) -> Self {
         Self::Msg(value)
@@ -0,0 +1,38 @@
+use std::{fmt::Display, io};
+
+use super::error::Error;
+
+/// Enum representing error when executing hook step, the first field is the
+/// step description (such as `post hook step 2`). Failed step contains the
+/// command, its exit code (none when terminated by a signal) and the tail of
+/// its stderr
+#[derive(Debug)]
+pub enum HookErr {
+    Expr(String, Box<Error>),
+    InvalidCommand(String, String),
+    Spawn(String, String, io::Error),
+    Failed(String, String, Option<i32>, String),
+}
+
+impl Display for HookErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookErr::Expr(s, e) => write!(f, "{s}: {e}"),
+            HookErr::InvalidCommand(s, e) => {
+                write!(f, "{s}: invalid command: {e}")
+            }
+            HookErr::Spawn(s, c, e) => write!(f, "{s}: running '{c}': {e}"),
+            HookErr::Failed(s, c, code, tail) => {
+                write!(f, "{s}: '{c}' ")?;
+                match code {
+                    Some(code) => write!(f, "failed with exit code {code}")?,
+                    None => write!(f, "was terminated by a signal")?,
+                }
+                for line in tail.lines() {
+                    write!(f, "\n  {line}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::{fmt::Display, path::PathBuf};
+
+use super::error::Error;
+
+/// Enum representing error when including file
+#[derive(Debug)]
+pub enum IncludeErr {
+    NotFound(String, Vec<PathBuf>),
+    Cycle(Vec<PathBuf>),
+    Failed(Vec<PathBuf>, Box<Error>),
+}
+
+impl IncludeErr {
+    /// Formats include chain
+    fn chain(chain: &[PathBuf]) -> String {
+        chain
+            .iter()
+            .map(|p| p.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+impl Display for IncludeErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IncludeErr::NotFound(n, c) if c.is_empty() => {
+                write!(f, "included file '{n}' not found")
+            }
+            IncludeErr::NotFound(n, c) => write!(
+                f,
+                "included file '{n}' not found (included from {})",
+                Self::chain(c)
+            ),
+            IncludeErr::Cycle(c) => {
+                write!(f, "include cycle detected: {}", Self::chain(c))
+            }
+            IncludeErr::Failed(c, e) => {
+                write!(f, "{e} (in {})", Self::chain(c))
+            }
+        }
+    }
+}
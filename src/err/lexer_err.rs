@@ -6,6 +6,8 @@ pub enum LexerErr {
     UnclosedLit,
     UnclosedBlock,
     UnexpectedToken,
+    UnknownFunc(String),
+    EnvNotAllowed(String),
 }
 
 impl Display for LexerErr {
@@ -15,6 +17,13 @@ impl Display for LexerErr {
             LexerErr::UnclosedLit => write!(f, "unclosed literal"),
             LexerErr::UnclosedBlock => write!(f, "code block not closed"),
             LexerErr::UnexpectedToken => write!(f, "unexpected token"),
+            LexerErr::UnknownFunc(name) => {
+                write!(f, "unknown function '{name}'")
+            }
+            LexerErr::EnvNotAllowed(name) => write!(
+                f,
+                "environment variable '{name}' is not allowed by the template"
+            ),
         }
     }
 }
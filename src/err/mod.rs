@@ -1,4 +1,9 @@
+pub mod archive_err;
 pub mod args_err;
+pub mod config_err;
 pub mod error;
+pub mod hook_err;
+pub mod include_err;
 pub mod lexer_err;
+pub mod source_err;
 pub mod template_err;
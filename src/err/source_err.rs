@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+/// Enum representing error when adding or upgrading template from a source
+#[derive(Debug)]
+pub enum SourceErr {
+    NotFound(String),
+    NoManifest(String),
+    NoSource(String),
+    Invalid(String),
+    Git(String),
+}
+
+impl Display for SourceErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceErr::NotFound(s) => write!(f, "source '{s}' not found"),
+            SourceErr::NoManifest(s) => {
+                write!(f, "source '{s}' doesn't contain template manifest")
+            }
+            SourceErr::NoSource(n) => write!(
+                f,
+                "template '{n}' wasn't added from a source, it can't be \
+                 upgraded"
+            ),
+            SourceErr::Invalid(n) => {
+                write!(f, "template '{n}' has invalid source")
+            }
+            SourceErr::Git(e) => write!(f, "git: {e}"),
+        }
+    }
+}
@@ -1,18 +1,55 @@
 use std::fmt::Display;
 
+use super::error::Error;
+
 #[derive(Debug)]
 pub enum TemplateErr {
     NotFound(String),
-    PreExec,
-    PostExec,
+    Exists(String),
+    InvalidName(String),
+    DependencyCycle(String),
+    ExtendsCycle(String),
+    TooManyArgs(String, usize),
+    NoManifest(String),
+    InvalidDelims,
+    Computed(String, Box<Error>),
+    UntrustedHooks(String),
 }
 
 impl Display for TemplateErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TemplateErr::NotFound(n) => write!(f, "template '{n}' not found"),
-            TemplateErr::PreExec => write!(f, "executing pre script"),
-            TemplateErr::PostExec => write!(f, "executing post script"),
+            TemplateErr::Exists(n) => {
+                write!(f, "template '{n}' already exists")
+            }
+            TemplateErr::InvalidName(n) => {
+                write!(f, "invalid template name '{n}'")
+            }
+            TemplateErr::DependencyCycle(c) => {
+                write!(f, "template dependency cycle: {c}")
+            }
+            TemplateErr::ExtendsCycle(c) => {
+                write!(f, "template inheritance cycle: {c}")
+            }
+            TemplateErr::TooManyArgs(n, c) => {
+                write!(f, "generator '{n}' takes at most {c} arguments")
+            }
+            TemplateErr::NoManifest(n) => write!(
+                f,
+                "template '{n}' has no makeit.json, makeit.toml or makeit.yaml"
+            ),
+            TemplateErr::InvalidDelims => {
+                write!(f, "invalid code block delimiters")
+            }
+            TemplateErr::Computed(n, e) => {
+                write!(f, "computing variable '{n}': {e}")
+            }
+            TemplateErr::UntrustedHooks(n) => write!(
+                f,
+                "template '{n}' has untrusted hooks, use '--trust-scripts' \
+                to run them or '--no-scripts' to skip them"
+            ),
         }
     }
 }
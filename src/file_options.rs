@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::parse::delims::Delims;
+
 /// Represents file action
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum FileAction {
@@ -23,4 +25,12 @@ pub struct FileOptions {
     pub action: FileAction,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delimiters: Option<Delims>,
+    #[serde(
+        default,
+        rename = "dropBlockLines",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub drop_block_lines: Option<bool>,
 }
@@ -0,0 +1,85 @@
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::err::{args_err::ArgsErr, error::Error};
+
+/// Format of the data files, such as template manifest or answers file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    /// All formats in the order template manifests are looked up in
+    pub const ALL: [Format; 3] = [Format::Json, Format::Toml, Format::Yaml];
+
+    /// Gets format based on the file extension (`toml`, `yaml`, `yml`,
+    /// otherwise JSON)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+            "toml" => Format::Toml,
+            "yaml" | "yml" => Format::Yaml,
+            _ => Format::Json,
+        }
+    }
+
+    /// Gets file name of the template manifest in this format
+    pub fn manifest(&self) -> &'static str {
+        match self {
+            Format::Json => "makeit.json",
+            Format::Toml => "makeit.toml",
+            Format::Yaml => "makeit.yaml",
+        }
+    }
+
+    /// Deserializes given text in this format
+    pub fn deserialize<T>(&self, text: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(match self {
+            Format::Json => serde_json::from_str(text)?,
+            Format::Toml => toml::from_str(text)?,
+            Format::Yaml => serde_yaml::from_str(text)?,
+        })
+    }
+
+    /// Serializes given value to this format
+    pub fn serialize<T>(&self, val: &T) -> Result<String, Error>
+    where
+        T: Serialize,
+    {
+        Ok(match self {
+            Format::Json => serde_json::to_string_pretty(val)?,
+            Format::Toml => toml::to_string_pretty(val)?,
+            Format::Yaml => serde_yaml::to_string(val)?,
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = ArgsErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "toml" => Ok(Format::Toml),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            _ => Err(ArgsErr::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Json => write!(f, "json"),
+            Format::Toml => write!(f, "toml"),
+            Format::Yaml => write!(f, "yaml"),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    io::{self, stderr, Read, Write},
+    process::{Command, ExitStatus, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of the last stderr lines of the failed command kept for the error
+pub const STDERR_TAIL: usize = 10;
+/// Maximum number of bytes of stderr kept to get its tail from
+const STDERR_BUF: usize = 64 * 1024;
+
+/// Steps of the pre or post hook, executed in order. In the manifest it can
+/// be a single command, single step or list of commands and steps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "HooksRepr", into = "HooksRepr")]
+pub struct Hooks(pub Vec<Step>);
+
+/// Single step of the hook
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    /// Command to run (code blocks can be used)
+    pub command: String,
+    /// Expression (without delimiters), step is skipped when it's falsy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// Working directory relative to the project (code blocks can be used)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Runs command using the system shell
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shell: bool,
+    /// Hides output of the command, only stderr tail is shown on failure
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quiet: bool,
+    /// Continues with the next steps when this step fails
+    #[serde(
+        default,
+        rename = "continueOnError",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub continue_on_error: bool,
+}
+
+impl Hooks {
+    /// Checks whether there are no steps
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Option<String>> for Hooks {
+    fn from(value: Option<String>) -> Self {
+        Self(value.into_iter().map(Step::from).collect())
+    }
+}
+
+impl From<String> for Step {
+    fn from(value: String) -> Self {
+        Self {
+            command: value,
+            ..Default::default()
+        }
+    }
+}
+
+impl Step {
+    /// Checks whether step contains only the command
+    fn is_command(&self) -> bool {
+        *self == Step::from(self.command.clone())
+    }
+}
+
+/// Runs the command and waits for it to finish. Output is streamed to the
+/// terminal as the command runs, unless `quiet` is set. Returns the exit
+/// status and the last [`STDERR_TAIL`] lines of stderr
+pub fn run_command(
+    command: &mut Command,
+    quiet: bool,
+) -> io::Result<(ExitStatus, String)> {
+    let stdout = if quiet {
+        Stdio::null()
+    } else {
+        Stdio::inherit()
+    };
+    let mut child = command.stdout(stdout).stderr(Stdio::piped()).spawn()?;
+
+    let mut err = vec![];
+    if let Some(mut pipe) = child.stderr.take() {
+        let mut buf = [0; 4096];
+        loop {
+            let n = match pipe.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if !quiet {
+                _ = stderr().write_all(&buf[..n]);
+            }
+            err.extend_from_slice(&buf[..n]);
+            if err.len() > STDERR_BUF {
+                err.drain(..err.len() - STDERR_BUF);
+            }
+        }
+    }
+    let status = child.wait()?;
+
+    let err = String::from_utf8_lossy(&err);
+    let lines: Vec<_> = err.trim_end().lines().collect();
+    let tail = lines[lines.len().saturating_sub(STDERR_TAIL)..].join("\n");
+    Ok((status, tail))
+}
+
+/// Representation of the step in the manifest
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StepRepr {
+    Command(String),
+    Step(Step),
+}
+
+/// Representation of the hook in the manifest. List has to be first,
+/// because step can be deserialized from a sequence of its fields
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum HooksRepr {
+    Many(Vec<StepRepr>),
+    One(StepRepr),
+}
+
+impl From<StepRepr> for Step {
+    fn from(value: StepRepr) -> Self {
+        match value {
+            StepRepr::Command(cmd) => Step::from(cmd),
+            StepRepr::Step(step) => step,
+        }
+    }
+}
+
+impl From<Step> for StepRepr {
+    fn from(value: Step) -> Self {
+        if value.is_command() {
+            StepRepr::Command(value.command)
+        } else {
+            StepRepr::Step(value)
+        }
+    }
+}
+
+impl From<HooksRepr> for Hooks {
+    fn from(value: HooksRepr) -> Self {
+        match value {
+            HooksRepr::One(step) => Self(vec![step.into()]),
+            HooksRepr::Many(steps) => {
+                Self(steps.into_iter().map(Step::from).collect())
+            }
+        }
+    }
+}
+
+impl From<Hooks> for HooksRepr {
+    fn from(mut value: Hooks) -> Self {
+        if value.0.len() == 1 {
+            HooksRepr::One(value.0.remove(0).into())
+        } else {
+            HooksRepr::Many(value.0.into_iter().map(StepRepr::from).collect())
+        }
+    }
+}
@@ -1,8 +1,16 @@
+pub mod archive;
 pub mod args;
+pub mod builtins;
+pub mod bundled;
 pub mod config;
 pub mod err;
 pub mod file_options;
+pub mod format;
+pub mod hooks;
 pub mod parse;
 pub mod prompt;
+pub mod source;
 pub mod template;
+pub mod trust;
+pub mod vars;
 pub mod writer;
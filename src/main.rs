@@ -1,19 +1,29 @@
-use std::{env, process};
+use std::{path::Path, process};
 
 use args::{Action, Args};
+use builtins::Builtins;
 use config::Config;
-use err::error::Error;
+use err::{args_err::ArgsErr, error::Error};
 use termint::{enums::fg::Fg, widgets::span::StrSpanExtension};
+use vars::{VarLayer, Vars};
 
 use crate::template::Template;
 
+mod archive;
 mod args;
+mod builtins;
+mod bundled;
 mod config;
 mod err;
 mod file_options;
+mod format;
+mod hooks;
 mod parse;
 mod prompt;
+mod source;
 mod template;
+mod trust;
+mod vars;
 mod writer;
 
 fn main() {
@@ -24,30 +34,110 @@ fn main() {
 }
 
 fn run() -> Result<(), Error> {
-    let mut args = Args::parse(std::env::args())?;
+    let args = Args::parse(std::env::args())?;
 
-    let config = Config::load()?;
+    let mut config = Config::load(args.config.as_ref().map(Path::new))?;
     match args.action {
         Some(Action::Create) => create(&config, args),
         Some(Action::Remove) => remove(&config, &args),
+        Some(Action::Rename) => Template::rename(&config, &args),
+        Some(Action::Copy) => Template::copy(&config, &args),
         Some(Action::List) => Template::list(&config),
-        Some(Action::Help) => Ok(Args::help()),
-        Some(Action::Version) => Ok(version()),
-        _ => load(&config, &mut args),
+        Some(Action::ShowVars) => show_vars(&config, &args),
+        Some(Action::SetGlobal) => set_global(&mut config, args),
+        Some(Action::UnsetGlobal) => unset_global(&mut config, &args),
+        Some(Action::ListGlobal) => {
+            list_global(&config);
+            Ok(())
+        }
+        Some(Action::ShowConfig) => show_config(&config),
+        Some(Action::InstallBuiltin) => bundled::install(&config, &args),
+        Some(Action::Export) => export(&config, &args),
+        Some(Action::Import) => import(&config, &args),
+        Some(Action::Add) => source::add(&config, &args),
+        Some(Action::Upgrade) => source::upgrade(&config, &args),
+        Some(Action::Help) => {
+            Args::help();
+            Ok(())
+        }
+        Some(Action::Version) => {
+            version();
+            Ok(())
+        }
+        _ => load(&config, &args),
     }
 }
 
-fn load(config: &Config, args: &mut Args) -> Result<(), Error> {
+fn load(config: &Config, args: &Args) -> Result<(), Error> {
     args.check_template()?;
+    Template::load(config, args, get_vars(config, args)?)
+}
+
+fn show_vars(config: &Config, args: &Args) -> Result<(), Error> {
+    args.check_template()?;
+    Template::show_vars(config, args, get_vars(config, args)?)
+}
+
+fn set_global(config: &mut Config, args: Args) -> Result<(), Error> {
+    config.vars.extend(args.vars);
+    Ok(config.save()?)
+}
 
-    let dst = args.get_path();
-    if let Some(name) = dst.file_name() {
-        args.add_var("_PNAME", name.to_string_lossy().to_string());
+fn unset_global(config: &mut Config, args: &Args) -> Result<(), Error> {
+    for name in args.vars.keys() {
+        config.vars.remove(name);
     }
-    args.add_var("_PDIR", dst.to_string_lossy().to_string());
-    args.add_var("_OS", env::consts::OS.to_string());
+    Ok(config.save()?)
+}
 
-    Template::load(config, args)
+fn list_global(config: &Config) {
+    let mut vars: Vec<_> = config.vars.iter().collect();
+    vars.sort_by_key(|(name, _)| *name);
+    for (name, val) in vars {
+        println!("{} = {val}", name.fg(Fg::Green));
+    }
+}
+
+/// Prints path of the config file, the effective config and the template
+/// directories
+fn show_config(config: &Config) -> Result<(), Error> {
+    print!("{} {}", "Config file:".fg(Fg::Green), config.path.display());
+    if !config.path.exists() {
+        print!(" {}", "(not found, using defaults)".fg(Fg::Gray));
+    }
+    println!();
+    println!("{}", serde_json::to_string_pretty(config)?);
+
+    println!("{}", "Template directories:".fg(Fg::Green));
+    for dir in config.template_dirs() {
+        println!(
+            "  {} {}",
+            dir.path.display(),
+            format!("({})", dir.source).fg(Fg::Gray)
+        );
+    }
+    Ok(())
+}
+
+/// Gets variables from all the layers, except the template ones
+fn get_vars(config: &Config, args: &Args) -> Result<Vars, Error> {
+    let mut vars = Vars::default();
+
+    let builtins = Builtins::new(
+        &args.get_path(),
+        args.template.clone(),
+        config.date_format.as_deref(),
+    )?;
+    vars.set_builtins(builtins);
+
+    vars.extend(VarLayer::Global, config.vars.clone());
+
+    if let Some(file) = &args.vars_file {
+        vars.extend(VarLayer::Answers, Vars::file(Path::new(file))?);
+    }
+    vars.extend(VarLayer::Env, Vars::env());
+    vars.extend(VarLayer::Args, args.vars.clone());
+    Ok(vars)
 }
 
 fn create(config: &Config, args: Args) -> Result<(), Error> {
@@ -55,6 +145,16 @@ fn create(config: &Config, args: Args) -> Result<(), Error> {
     Template::create(config, args)
 }
 
+fn export(config: &Config, args: &Args) -> Result<(), Error> {
+    let file = args.target.as_ref().ok_or(ArgsErr::MissingParam)?;
+    archive::export(config, args, Path::new(file))
+}
+
+fn import(config: &Config, args: &Args) -> Result<(), Error> {
+    let file = args.archive.as_ref().ok_or(ArgsErr::MissingParam)?;
+    archive::import(config, args, Path::new(file))
+}
+
 fn remove(config: &Config, args: &Args) -> Result<(), Error> {
     args.check_template()?;
     Template::remove(config, args)
@@ -1,11 +1,67 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
-/// Represents value that expression returns
-#[derive(Debug, Clone, PartialEq)]
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Represents value of the variable and value that expression returns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum Value {
-    String(String),
-    Bool(bool),
     Null,
+    Bool(bool),
+    #[serde(serialize_with = "serialize_number")]
+    Number(f64),
+    String(String),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Checks whether value is considered true in conditions
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Bool(false) | Value::Null)
+    }
+
+    /// Converts value to string value, which is how all the values behaved
+    /// before variables were typed. Returns [`None`] for null
+    pub fn to_compat(&self) -> Option<Value> {
+        match self {
+            Value::Null => None,
+            Value::String(_) => Some(self.clone()),
+            val => Some(Value::String(val.to_string())),
+        }
+    }
+
+    /// Infers type of the value from its text. `true` and `false` are
+    /// booleans, numbers written the way they are printed are numbers and
+    /// everything else is a string
+    pub fn infer(text: &str) -> Value {
+        match text {
+            "true" => return Value::Bool(true),
+            "false" => return Value::Bool(false),
+            _ => {}
+        }
+        match text.parse::<f64>() {
+            Ok(n) if n.is_finite() && Value::Number(n).to_string() == text => {
+                Value::Number(n)
+            }
+            _ => Value::String(text.to_string()),
+        }
+    }
+}
+
+/// Serializes whole numbers as integers, so they aren't written with `.0`
+fn serialize_number<S>(n: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        serializer.serialize_i64(*n as i64)
+    } else {
+        serializer.serialize_f64(*n)
+    }
 }
 
 impl Display for Value {
@@ -14,11 +70,42 @@ impl Display for Value {
             Value::String(s) => write!(f, "{s}"),
             Value::Bool(true) => write!(f, "true"),
             Value::Bool(false) => write!(f, "false"),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => {
+                write!(f, "{}", *n as i64)
+            }
+            Value::Number(n) => write!(f, "{n}"),
+            Value::List(l) => {
+                let items: Vec<_> = l.iter().map(|i| i.to_string()).collect();
+                write!(f, "{}", items.join(", "))
+            }
+            Value::Map(m) => {
+                let items: Vec<_> =
+                    m.iter().map(|(k, v)| format!("{k}: {v}")).collect();
+                write!(f, "{}", items.join(", "))
+            }
             Value::Null => write!(f, "null"),
         }
     }
 }
 
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Expr {
     Var(VarExpr),
@@ -31,7 +118,7 @@ pub enum Expr {
 }
 
 impl Expr {
-    pub fn eval(&self, vars: &HashMap<String, String>) -> Value {
+    pub fn eval(&self, vars: &HashMap<String, Value>) -> Value {
         match self {
             Expr::Var(v) => v.eval(vars),
             Expr::Lit(l) => l.eval(vars),
@@ -70,19 +157,15 @@ macro_rules! expr_struct {
 expr_struct!(VarExpr { name: String });
 
 impl VarExpr {
-    fn eval(&self, vars: &HashMap<String, String>) -> Value {
-        if let Some(val) = vars.get(&self.name) {
-            Value::String(val.to_owned())
-        } else {
-            Value::Null
-        }
+    fn eval(&self, vars: &HashMap<String, Value>) -> Value {
+        vars.get(&self.name).cloned().unwrap_or(Value::Null)
     }
 }
 
 expr_struct!(LitExpr { value: Value });
 
 impl LitExpr {
-    fn eval(&self, _vars: &HashMap<String, String>) -> Value {
+    fn eval(&self, _vars: &HashMap<String, Value>) -> Value {
         self.value.clone()
     }
 }
@@ -94,12 +177,11 @@ expr_struct!(CheckExpr {
 });
 
 impl CheckExpr {
-    fn eval(&self, vars: &HashMap<String, String>) -> Value {
-        let cond = self.cond.eval(vars);
-
-        match cond {
-            Value::Bool(false) | Value::Null => self.right.eval(vars),
-            _ => self.left.eval(vars),
+    fn eval(&self, vars: &HashMap<String, Value>) -> Value {
+        if self.cond.eval(vars).is_truthy() {
+            self.left.eval(vars)
+        } else {
+            self.right.eval(vars)
         }
     }
 }
@@ -110,7 +192,7 @@ expr_struct!(NullCheckExpr {
 });
 
 impl NullCheckExpr {
-    fn eval(&self, vars: &HashMap<String, String>) -> Value {
+    fn eval(&self, vars: &HashMap<String, Value>) -> Value {
         let res = self.left.eval(vars);
 
         match res {
@@ -126,7 +208,7 @@ expr_struct!(EqualsExpr {
 });
 
 impl EqualsExpr {
-    fn eval(&self, vars: &HashMap<String, String>) -> Value {
+    fn eval(&self, vars: &HashMap<String, Value>) -> Value {
         let left = self.left.eval(vars);
         let right = self.right.eval(vars);
 
@@ -140,10 +222,17 @@ expr_struct!(AddExpr {
 });
 
 impl AddExpr {
-    fn eval(&self, vars: &HashMap<String, String>) -> Value {
+    fn eval(&self, vars: &HashMap<String, Value>) -> Value {
         let left = self.left.eval(vars);
         let right = self.right.eval(vars);
 
-        Value::String(left.to_string() + &right.to_string())
+        match (left, right) {
+            (Value::Number(l), Value::Number(r)) => Value::Number(l + r),
+            (Value::List(mut l), Value::List(r)) => {
+                l.extend(r);
+                Value::List(l)
+            }
+            (l, r) => Value::String(l.to_string() + &r.to_string()),
+        }
     }
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents opening and closing sequence of the code block
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Delims {
+    pub open: String,
+    pub close: String,
+}
+
+impl Delims {
+    /// Creates new [`Delims`] with given opening and closing sequence
+    pub fn new<T, U>(open: T, close: U) -> Self
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        Self {
+            open: open.as_ref().to_string(),
+            close: close.as_ref().to_string(),
+        }
+    }
+
+    /// Checks whether delimiters can be used - none of them can be empty
+    /// and opening sequence can't start with escape character
+    pub fn is_valid(&self) -> bool {
+        !self.open.is_empty()
+            && !self.close.is_empty()
+            && !self.open.starts_with('\\')
+    }
+}
+
+impl Default for Delims {
+    fn default() -> Self {
+        Self::new("{{", "}}")
+    }
+}
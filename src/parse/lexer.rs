@@ -1,20 +1,27 @@
-use std::io;
+use std::{collections::VecDeque, io};
 
 use crate::err::lexer_err::LexerErr;
 
+use super::delims::Delims;
+
 /// Represents token read by the lexer
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub enum Token {
     Colon,
     Question,
     NullCheck,
     Equals,
+    Assign,
     Ident(String),
+    /// Identifier directly followed by opening parenthesis
+    Func(String),
     Literal(String),
+    Number(f64),
     OpenParen,
     CloseParen,
     Plus,
     End,
+    TrimEnd,
 }
 
 /// Provides lexical analysis of the text
@@ -24,6 +31,8 @@ where
     I: Iterator<Item = Result<char, io::Error>>,
 {
     text: &'a mut I,
+    peeked: VecDeque<char>,
+    pub delims: Delims,
     pub cur: Option<char>,
 }
 
@@ -32,9 +41,11 @@ where
     I: Iterator<Item = Result<char, io::Error>>,
 {
     /// Creates new [`Lexer`]
-    pub fn new(text: &'a mut I) -> Self {
+    pub fn new(text: &'a mut I, delims: Delims) -> Self {
         let mut lex = Self {
-            text: text,
+            text,
+            peeked: VecDeque::new(),
+            delims,
             cur: None,
         };
         lex.next_char();
@@ -42,27 +53,30 @@ where
     }
 
     /// Gets next [`Token`]
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Token, LexerErr> {
         self.skip_whitespace();
 
+        let close = self.delims.close.clone();
+        if self.starts_with(&close) {
+            self.skip(close.chars().count() - 1);
+            return Ok(Token::End);
+        }
+        if self.starts_with(&format!("-{close}")) {
+            self.skip(close.chars().count());
+            return Ok(Token::TrimEnd);
+        }
+
         match self.cur {
             Some('?') => Ok(self.read_question()),
             Some(':') => {
                 self.next_char();
                 Ok(Token::Colon)
             }
-            Some('=') => self.read_equals(),
+            Some('=') => Ok(self.read_equals()),
             Some('"') => self.read_literal(),
+            Some(c) if c.is_ascii_digit() => self.read_number(),
             Some(c) if c.is_alphabetic() || c == '_' => Ok(self.read_ident()),
-            Some('}') => {
-                self.next_char();
-                if self.cur == Some('}') {
-                    Ok(Token::End)
-                } else {
-                    self.next_char();
-                    Err(LexerErr::InvalidToken)
-                }
-            }
             Some('(') => {
                 self.next_char();
                 Ok(Token::OpenParen)
@@ -82,12 +96,46 @@ where
 
     /// Gets next char from the text
     pub fn next_char(&mut self) {
+        if let Some(c) = self.peeked.pop_front() {
+            self.cur = Some(c);
+            return;
+        }
+
         match self.text.next() {
             Some(Ok(c)) => self.cur = Some(c),
             _ => self.cur = None,
         }
     }
 
+    /// Checks whether text starting with current char starts with given
+    /// sequence, without consuming any characters
+    pub fn starts_with(&mut self, seq: &str) -> bool {
+        let mut chars = seq.chars();
+        if chars.next() != self.cur || self.cur.is_none() {
+            return false;
+        }
+
+        for (i, c) in chars.enumerate() {
+            if i >= self.peeked.len() {
+                match self.text.next() {
+                    Some(Ok(p)) => self.peeked.push_back(p),
+                    _ => return false,
+                }
+            }
+            if self.peeked[i] != c {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Skips given number of characters
+    pub fn skip(&mut self, cnt: usize) {
+        for _ in 0..cnt {
+            self.next_char();
+        }
+    }
+
     /// Reads question or null check
     fn read_question(&mut self) -> Token {
         self.next_char();
@@ -101,16 +149,16 @@ where
         }
     }
 
-    /// Reads equals
-    fn read_equals(&mut self) -> Result<Token, LexerErr> {
+    /// Reads equals or assignment
+    fn read_equals(&mut self) -> Token {
         self.next_char();
 
         match self.cur {
             Some('=') => {
                 self.next_char();
-                Ok(Token::Equals)
+                Token::Equals
             }
-            _ => Err(LexerErr::InvalidToken),
+            _ => Token::Assign,
         }
     }
 
@@ -125,9 +173,30 @@ where
             res.push(c);
             self.next_char();
         }
+
+        if self.cur == Some('(') {
+            self.next_char();
+            return Token::Func(res);
+        }
         Token::Ident(res)
     }
 
+    /// Reads number literal
+    fn read_number(&mut self) -> Result<Token, LexerErr> {
+        let mut res = String::new();
+        while let Some(c) = self.cur {
+            if !c.is_ascii_digit() && c != '.' {
+                break;
+            }
+
+            res.push(c);
+            self.next_char();
+        }
+        res.parse()
+            .map(Token::Number)
+            .map_err(|_| LexerErr::InvalidToken)
+    }
+
     /// Reads literal
     fn read_literal(&mut self) -> Result<Token, LexerErr> {
         self.next_char();
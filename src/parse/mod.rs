@@ -1,3 +1,5 @@
 pub mod ast;
+pub mod delims;
 pub mod lexer;
 pub mod parser;
+pub mod parser_options;
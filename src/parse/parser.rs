@@ -1,12 +1,15 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fs::File,
-    io::{self, BufWriter},
-    path::PathBuf,
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
 };
 
+use utf8_chars::BufReadCharsExt;
+
 use crate::{
-    err::{error::Error, lexer_err::LexerErr},
+    err::{error::Error, include_err::IncludeErr, lexer_err::LexerErr},
     writer::Writer,
 };
 
@@ -16,6 +19,7 @@ use super::{
         VarExpr,
     },
     lexer::{Lexer, Token},
+    parser_options::ParserOptions,
 };
 
 pub struct Parser<'a, I>
@@ -24,8 +28,19 @@ where
 {
     lexer: Lexer<'a, I>,
     output: Writer<'a>,
-    vars: &'a HashMap<String, String>,
+    vars: Cow<'a, HashMap<String, Value>>,
     token: Option<Token>,
+    opts: ParserOptions,
+    /// Whitespace that wasn't written yet, so it can be trimmed
+    pending: String,
+    /// Position in `pending` where the current line starts
+    line_start: usize,
+    /// Whether current line contains text or non-empty code block
+    line_text: bool,
+    /// Whether current line contains code block evaluated to empty string
+    line_block: bool,
+    /// Whether following whitespace should be skipped
+    trim: bool,
 }
 
 impl<'a, I> Parser<'a, I>
@@ -34,98 +49,306 @@ where
 {
     /// Creates new [`Parser`] that outputs to stdout
     /// This exists for testing purposes
-    #[allow(unused)]
+    #[allow(unused, clippy::new_ret_no_self)]
     pub fn new(
         text: &'a mut I,
-        vars: &'a HashMap<String, String>,
+        vars: &'a HashMap<String, Value>,
+        opts: &ParserOptions,
     ) -> Result<(), Error> {
-        let mut parser = Self {
-            lexer: Lexer::new(text),
-            output: Writer::Stdout,
-            vars,
-            token: None,
-        };
-        parser.parse()
+        Self::init(text, vars, Writer::Stdout, opts).parse()
     }
 
     /// Creates new [`Parser`] that outputs to the file
     pub fn file(
         text: &'a mut I,
-        vars: &'a HashMap<String, String>,
-        file: &PathBuf,
+        vars: &'a HashMap<String, Value>,
+        file: &Path,
+        opts: &ParserOptions,
     ) -> Result<(), Error> {
-        let mut parser = Self {
-            lexer: Lexer::new(text),
-            output: Writer::File(BufWriter::<File>::new(File::create(file)?)),
-            vars,
-            token: None,
-        };
-        parser.parse()
+        let output = Writer::File(BufWriter::<File>::new(File::create(file)?));
+        Self::init(text, vars, output, opts).parse()
     }
 
     /// Creates new [`Parser`] that outputs to the given string
     pub fn string(
         text: &'a mut I,
-        vars: &'a HashMap<String, String>,
+        vars: &'a HashMap<String, Value>,
         out: &'a mut String,
+        opts: &ParserOptions,
     ) -> Result<(), Error> {
-        let mut parser = Self {
-            lexer: Lexer::new(text),
-            output: Writer::String(out),
-            vars,
+        Self::init(text, vars, Writer::String(out), opts).parse()
+    }
+
+    /// Initializes the [`Parser`] with given output
+    fn init(
+        text: &'a mut I,
+        vars: &'a HashMap<String, Value>,
+        output: Writer<'a>,
+        opts: &ParserOptions,
+    ) -> Self {
+        Self {
+            lexer: Lexer::new(text, opts.delims.clone()),
+            output,
+            vars: Cow::Borrowed(vars),
             token: None,
-        };
-        parser.parse()
+            opts: opts.clone(),
+            pending: String::new(),
+            line_start: 0,
+            line_text: false,
+            line_block: false,
+            trim: false,
+        }
+    }
+
+    /// Evaluates given expression (without delimiters)
+    pub fn eval(
+        text: &'a mut I,
+        vars: &HashMap<String, Value>,
+        opts: &ParserOptions,
+    ) -> Result<Value, Error> {
+        let close = opts.delims.close.clone();
+        let mut text = text.chain(close.chars().map(Ok));
+        let mut out = String::new();
+        let mut parser =
+            Parser::init(&mut text, vars, Writer::String(&mut out), opts);
+
+        let expr = parser.parse_expr()?;
+        parser.expect_end()?;
+        Ok(expr.eval(&parser.vars))
     }
 
     /// Parses given text
     fn parse(&mut self) -> Result<(), Error> {
         while let Some(c) = self.lexer.cur {
             match c {
-                '{' => self.check_opening()?,
                 '\\' => self.handle_escape()?,
-                _ => self.output.write(c)?,
+                _ if self.check_opening() => self.handle_code()?,
+                _ => self.write_text(c)?,
             }
             self.lexer.next_char();
         }
-        Ok(())
+
+        if self.is_block_line() {
+            self.pending.truncate(self.line_start);
+        }
+        self.flush()
     }
 
     /// Handles escaping of the code block
     fn handle_escape(&mut self) -> Result<(), Error> {
         self.lexer.next_char();
+        let open = self.lexer.delims.open.chars().next();
         match self.lexer.cur {
-            Some('{') => self.output.write('{')?,
-            Some(c) => self.output.write_str(&format!("\\{c}"))?,
+            Some(c) if Some(c) == open => self.write_text(c)?,
+            Some(c) => {
+                self.write_text('\\')?;
+                self.write_text(c)?;
+            }
             _ => Err(LexerErr::UnclosedBlock)?,
         };
         Ok(())
     }
 
-    /// Checks for code block opening
-    fn check_opening(&mut self) -> Result<(), Error> {
-        self.lexer.next_char();
-        let Some(c) = self.lexer.cur else {
-            self.output.write('{')?;
-            return Ok(());
+    /// Checks for code block opening, skips it when found
+    fn check_opening(&mut self) -> bool {
+        let open = self.lexer.delims.open.clone();
+        if !self.lexer.starts_with(&open) {
+            return false;
+        }
+
+        self.lexer.skip(open.chars().count());
+        true
+    }
+
+    /// Handles code block
+    fn handle_code(&mut self) -> Result<(), Error> {
+        if self.lexer.cur == Some('-') {
+            self.pending.clear();
+            self.line_start = 0;
+            self.lexer.next_char();
+        }
+        if self.lexer.cur == Some('#') {
+            return self.handle_comment();
+        }
+
+        self.next_token()?;
+        if let Some(Token::Ident(keyword)) = &self.token {
+            match keyword.as_str() {
+                "include" => {
+                    self.token = None;
+                    return self.handle_include();
+                }
+                "set" | "let" => {
+                    self.token = None;
+                    return self.handle_set();
+                }
+                _ => {}
+            }
+        }
+
+        let expr = self.parse_expr()?;
+        self.trim = matches!(self.token.take(), Some(Token::TrimEnd));
+
+        let res = expr.eval(&self.vars).to_string();
+        self.write_block(&res)
+    }
+
+    /// Handles variable assignment, variable is set for the rest of the text
+    fn handle_set(&mut self) -> Result<(), Error> {
+        self.next_token()?;
+        let Some(Token::Ident(name)) = self.token.take() else {
+            return Err(LexerErr::UnexpectedToken.into());
+        };
+        self.next_token()?;
+        if !matches!(self.token.take(), Some(Token::Assign)) {
+            return Err(LexerErr::UnexpectedToken.into());
+        }
+
+        let expr = self.parse_expr()?;
+        self.expect_end()?;
+
+        match expr.eval(&self.vars) {
+            Value::Null => self.vars.to_mut().remove(&name),
+            val => self.vars.to_mut().insert(name, val),
+        };
+        self.write_block("")
+    }
+
+    /// Handles include - parses given file and writes its result
+    fn handle_include(&mut self) -> Result<(), Error> {
+        let expr = self.parse_expr()?;
+        self.trim = matches!(self.token.take(), Some(Token::TrimEnd));
+
+        let name = expr.eval(&self.vars).to_string();
+        let res = self.include(&name)?;
+        self.write_block(&res)
+    }
+
+    /// Parses included file with given name to string
+    fn include(&self, name: &str) -> Result<String, Error> {
+        let path = self.find_include(name)?;
+        let mut chain = self.opts.include_chain.clone();
+        chain.push(path.clone());
+        if self.opts.include_chain.contains(&path) {
+            return Err(IncludeErr::Cycle(chain).into());
+        }
+
+        let opts = ParserOptions {
+            include_chain: chain.clone(),
+            ..self.opts.clone()
         };
+        let mut res = String::new();
+        let mut buf = BufReader::new(File::open(&path)?);
+        let mut chars = buf.chars();
+        match Parser::string(&mut chars, &self.vars, &mut res, &opts) {
+            Ok(()) => Ok(res),
+            Err(e @ Error::IncludeErr(_)) => Err(e),
+            Err(e) => Err(IncludeErr::Failed(chain, Box::new(e)).into()),
+        }
+    }
 
-        if c != '{' {
-            self.output.write_str(&format!("{{{c}"))?;
+    /// Finds included file in the include directories
+    fn find_include(&self, name: &str) -> Result<PathBuf, IncludeErr> {
+        self.opts
+            .include_dirs
+            .iter()
+            .map(|d| d.join(name))
+            .find(|p| p.is_file())
+            .map(|p| p.canonicalize().unwrap_or(p))
+            .ok_or_else(|| {
+                IncludeErr::NotFound(
+                    name.to_string(),
+                    self.opts.include_chain.clone(),
+                )
+            })
+    }
+
+    /// Writes result of the code block
+    fn write_block(&mut self, res: &str) -> Result<(), Error> {
+        if res.is_empty() {
+            self.line_block = true;
             return Ok(());
         }
 
+        self.line_text = true;
+        self.flush()?;
+        self.output.write_str(res)?;
+        Ok(())
+    }
+
+    /// Handles comment - it is skipped and nothing is written
+    fn handle_comment(&mut self) -> Result<(), Error> {
+        let close = self.lexer.delims.close.clone();
+        let end = format!("#{close}");
+        let trim_end = format!("#-{close}");
+
         self.lexer.next_char();
-        self.handle_code()
+        while self.lexer.cur.is_some() {
+            if self.lexer.starts_with(&end) {
+                self.lexer.skip(end.chars().count() - 1);
+                self.trim = false;
+                break;
+            }
+            if self.lexer.starts_with(&trim_end) {
+                self.lexer.skip(trim_end.chars().count() - 1);
+                self.trim = true;
+                break;
+            }
+
+            self.lexer.next_char();
+        }
+
+        if self.lexer.cur.is_none() {
+            return Err(LexerErr::UnclosedBlock.into());
+        }
+        self.line_block = true;
+        Ok(())
     }
 
-    /// Handles code block
-    fn handle_code(&mut self) -> Result<(), Error> {
-        let expr = self.parse_expr()?;
-        self.output
-            .write_str(&format!("{}", expr.eval(&self.vars)))?;
-        self.token = None;
+    /// Writes text character, whitespace is postponed so it can be trimmed
+    fn write_text(&mut self, c: char) -> Result<(), Error> {
+        if self.trim && c.is_whitespace() {
+            return Ok(());
+        }
+        self.trim = false;
+
+        if c == '\n' {
+            self.end_line();
+        } else if c.is_whitespace() {
+            self.pending.push(c);
+        } else {
+            self.line_text = true;
+            self.flush()?;
+            self.output.write(c)?;
+        }
+        Ok(())
+    }
 
+    /// Ends current line, drops it when it contains only empty code blocks
+    fn end_line(&mut self) {
+        if self.is_block_line() {
+            self.pending.truncate(self.line_start);
+        } else {
+            self.pending.push('\n');
+        }
+
+        self.line_start = self.pending.len();
+        self.line_text = false;
+        self.line_block = false;
+    }
+
+    /// Checks whether current line should be dropped
+    fn is_block_line(&self) -> bool {
+        self.opts.drop_block_lines && self.line_block && !self.line_text
+    }
+
+    /// Writes postponed whitespace
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.pending.is_empty() {
+            self.output.write_str(&self.pending)?;
+            self.pending.clear();
+        }
+        self.line_start = 0;
         Ok(())
     }
 
@@ -142,8 +365,12 @@ where
                 Token::Ident(v) => {
                     prev = self.parse_var(prev, v.to_owned())?
                 }
+                Token::Func(f) => prev = self.parse_func(prev, f)?,
                 Token::Literal(v) => {
-                    prev = self.parse_lit(prev, v.to_owned())?
+                    prev = self.parse_lit(prev, Value::String(v))?
+                }
+                Token::Number(v) => {
+                    prev = self.parse_lit(prev, Value::Number(v))?
                 }
                 Token::OpenParen => return self.parse_paren(prev),
                 Token::Plus => prev = self.parse_plus(prev)?,
@@ -167,8 +394,12 @@ where
                 Token::Ident(v) => {
                     prev = self.parse_var(prev, v.to_owned())?
                 }
+                Token::Func(f) => prev = self.parse_func(prev, f)?,
                 Token::Literal(v) => {
-                    prev = self.parse_lit(prev, v.to_owned())?
+                    prev = self.parse_lit(prev, Value::String(v))?
+                }
+                Token::Number(v) => {
+                    prev = self.parse_lit(prev, Value::Number(v))?
                 }
                 Token::OpenParen => prev = self.parse_paren(prev)?,
                 Token::Plus => prev = self.parse_plus(prev)?,
@@ -218,18 +449,77 @@ where
         )))
     }
 
-    /// Parses variable
-    fn parse_var(&self, prev: Expr, name: String) -> Result<Expr, LexerErr> {
+    /// Parses variable, `true`, `false` and `null` are parsed as literals
+    fn parse_var(
+        &mut self,
+        prev: Expr,
+        name: String,
+    ) -> Result<Expr, LexerErr> {
+        match name.as_str() {
+            "true" => return self.parse_lit(prev, Value::Bool(true)),
+            "false" => return self.parse_lit(prev, Value::Bool(false)),
+            "null" => return self.parse_lit(prev, Value::Null),
+            _ => {}
+        }
+
         match prev {
-            Expr::None => Ok(Expr::Var(VarExpr::new(name))),
+            Expr::None => {
+                self.use_builtin(&name);
+                Ok(Expr::Var(VarExpr::new(name)))
+            }
             _ => Err(LexerErr::UnexpectedToken),
         }
     }
 
+    /// Adds internal variable with given name to the variables, when it's
+    /// not defined yet. Internal variable is computed only here
+    fn use_builtin(&mut self, name: &str) {
+        let Some(builtins) = &self.opts.builtins else {
+            return;
+        };
+        if self.vars.contains_key(name) {
+            return;
+        }
+
+        let val = match builtins.get(name) {
+            Some(val) if self.opts.string_vars => val.to_compat(),
+            val => val,
+        };
+        if let Some(val) = val {
+            self.vars.to_mut().insert(name.to_string(), val);
+        }
+    }
+
+    /// Parses function call, its opening parenthesis is already read
+    fn parse_func(
+        &mut self,
+        prev: Expr,
+        func: String,
+    ) -> Result<Expr, LexerErr> {
+        if !matches!(prev, Expr::None) {
+            return Err(LexerErr::UnexpectedToken);
+        }
+        if func != "env" {
+            return Err(LexerErr::UnknownFunc(func));
+        }
+
+        let Token::Literal(name) = self.lexer.next()? else {
+            return Err(LexerErr::UnexpectedToken);
+        };
+        if self.lexer.next()? != Token::CloseParen {
+            return Err(LexerErr::UnexpectedToken);
+        }
+
+        if !self.opts.env_allowed(&name) {
+            return Err(LexerErr::EnvNotAllowed(name));
+        }
+        Ok(Expr::Lit(LitExpr::new(self.opts.env_var(&name))))
+    }
+
     /// Parses literals
-    fn parse_lit(&self, prev: Expr, val: String) -> Result<Expr, LexerErr> {
+    fn parse_lit(&self, prev: Expr, val: Value) -> Result<Expr, LexerErr> {
         match prev {
-            Expr::None => Ok(Expr::Lit(LitExpr::new(Value::String(val)))),
+            Expr::None => Ok(Expr::Lit(LitExpr::new(val))),
             _ => Err(LexerErr::UnexpectedToken),
         }
     }
@@ -251,6 +541,16 @@ where
         Ok(Expr::Add(AddExpr::new(Box::new(prev), Box::new(right))))
     }
 
+    /// Checks whether code block ends, sets trimming based on the end
+    fn expect_end(&mut self) -> Result<(), LexerErr> {
+        match self.token.take() {
+            Some(Token::End) => self.trim = false,
+            Some(Token::TrimEnd) => self.trim = true,
+            _ => return Err(LexerErr::UnexpectedToken),
+        }
+        Ok(())
+    }
+
     /// Gets next token, when previous one is already taken
     fn next_token(&mut self) -> Result<(), LexerErr> {
         if self.token.is_none() {
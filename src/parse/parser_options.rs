@@ -0,0 +1,56 @@
+use std::{collections::HashMap, env, path::PathBuf, rc::Rc};
+
+use crate::builtins::Builtins;
+
+use super::ast::Value;
+
+use super::delims::Delims;
+
+/// Options that affect how the [`Parser`](super::parser::Parser) handles
+/// the text
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    /// Opening and closing sequence of the code block
+    pub delims: Delims,
+    /// Drops lines containing only code blocks that evaluate to empty string
+    pub drop_block_lines: bool,
+    /// Directories included files are searched in (in given order)
+    pub include_dirs: Vec<PathBuf>,
+    /// Files being parsed, starting with the outermost one
+    pub include_chain: Vec<PathBuf>,
+    /// Environment variables that can be read by `env` function, all of
+    /// them can be read when not set
+    pub allowed_env: Option<Vec<String>>,
+    /// Environment variables read by `env` function, the process
+    /// environment is used when not set
+    pub env: Option<HashMap<String, String>>,
+    /// Internal variables, they are computed when they're used and they
+    /// aren't defined by the variables
+    pub builtins: Option<Rc<Builtins>>,
+    /// Converts internal variables to strings (compatibility mode)
+    pub string_vars: bool,
+}
+
+impl ParserOptions {
+    /// Checks whether environment variable with given name can be read.
+    /// Allowed names can end with `*` to allow all names with given prefix
+    pub fn env_allowed(&self, name: &str) -> bool {
+        let Some(allowed) = &self.allowed_env else {
+            return true;
+        };
+        allowed.iter().any(|a| match a.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => a == name,
+        })
+    }
+
+    /// Gets value of the environment variable with given name, it's null
+    /// when the variable isn't set
+    pub fn env_var(&self, name: &str) -> Value {
+        let val = match &self.env {
+            Some(env) => env.get(name).cloned(),
+            None => env::var(name).ok(),
+        };
+        val.map(Value::String).unwrap_or(Value::Null)
+    }
+}
@@ -17,6 +17,38 @@ pub fn not_empty_prompt(auto_yes: bool) -> bool {
     )
 }
 
+pub fn overwrite_prompt(auto_yes: bool) -> bool {
+    yes_no(
+        auto_yes,
+        "Some of the generated files already exist.\nDo you want to \
+         overwrite them?",
+    )
+}
+
+pub fn hooks_prompt(auto_yes: bool) -> bool {
+    no_yes(auto_yes, "Do you want to run these commands?")
+}
+
+/// Creates yes or no prompt with no as default option, no is also the
+/// answer when there is no input
+pub fn no_yes<T>(auto_yes: bool, question: T) -> bool
+where
+    T: AsRef<str>,
+{
+    if auto_yes {
+        return true;
+    }
+
+    print!("{} [y/N]: ", question.as_ref());
+    _ = stdout().flush();
+    let stdin = stdin();
+    let Some(Ok(answer)) = stdin.lock().lines().next() else {
+        return false;
+    };
+
+    matches!(&*answer.to_lowercase(), "y" | "yes")
+}
+
 /// Creates yes or no prompt with yes as default option
 pub fn yes_no<T>(auto_yes: bool, question: T) -> bool
 where
@@ -31,8 +63,5 @@ where
     let stdin = stdin();
     let answer = stdin.lock().lines().next().unwrap().unwrap();
 
-    match &*answer.to_lowercase() {
-        "n" | "no" => false,
-        _ => true,
-    }
+    !matches!(&*answer.to_lowercase(), "n" | "no")
 }
@@ -0,0 +1,289 @@
+use std::{
+    fs::{canonicalize, copy, create_dir_all, read_dir, read_to_string},
+    fs::{remove_dir_all, write},
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use serde::{Deserialize, Serialize};
+use termint::{enums::fg::Fg, widgets::span::StrSpanExtension};
+
+use crate::{
+    archive::{dir_files, safe_path},
+    args::Args,
+    config::Config,
+    err::{
+        args_err::ArgsErr, error::Error, source_err::SourceErr,
+        template_err::TemplateErr,
+    },
+    prompt::replace_prompt,
+    template::Template,
+};
+
+/// File in the template directory, which records the template source
+pub const SOURCE_FILE: &str = ".makeit-source.json";
+
+/// Kind of the template source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// Directory on the filesystem
+    Path,
+    /// Git repository (local path or `file://` URL)
+    Git,
+}
+
+/// Source the template was added from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Source {
+    pub kind: SourceKind,
+    /// Path of the directory or location of the git repository
+    pub location: String,
+    /// Directory of the template in the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Git commit the template was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+}
+
+/// Temporary directory with git checkout, removed when dropped
+struct Checkout(PathBuf);
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        _ = remove_dir_all(&self.0);
+    }
+}
+
+impl Source {
+    /// Parses source given by the user. Local paths are made absolute and
+    /// they are considered git repositories when they contain `.git` or
+    /// when they are bare repositories
+    pub fn parse(src: &str) -> Result<Self, SourceErr> {
+        let (kind, location) = if src.starts_with("file://") {
+            (SourceKind::Git, src.to_string())
+        } else {
+            let path = canonicalize(src)
+                .map_err(|_| SourceErr::NotFound(src.to_string()))?;
+            let kind = if path.join(".git").exists()
+                || (path.join("HEAD").is_file()
+                    && path.join("objects").is_dir())
+            {
+                SourceKind::Git
+            } else {
+                SourceKind::Path
+            };
+            (kind, path.to_string_lossy().to_string())
+        };
+
+        Ok(Self {
+            kind,
+            location,
+            subdir: None,
+            revision: None,
+        })
+    }
+
+    /// Gets default template name based on the source location (its last
+    /// component without `.git` extension)
+    pub fn name(&self) -> Option<String> {
+        let last = self.location.trim_end_matches('/').rsplit('/').next()?;
+        let name = last.strip_suffix(".git").unwrap_or(last);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Loads source of the template in given directory, returns [`None`]
+    /// when template wasn't added from a source. Fails when the source
+    /// isn't valid
+    pub fn load(dir: &Path) -> Result<Option<Self>, Error> {
+        let path = dir.join(SOURCE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let source: Self = serde_json::from_str(&read_to_string(path)?)?;
+        if !source.is_valid() {
+            let name = dir.file_name().unwrap_or_default().to_string_lossy();
+            return Err(SourceErr::Invalid(name.to_string()).into());
+        }
+        Ok(Some(source))
+    }
+
+    /// Checks whether the source is valid. Location has to be absolute path
+    /// (or `file://` URL for git repository) and subdirectory can't leave
+    /// the source
+    fn is_valid(&self) -> bool {
+        let location = match self.kind {
+            SourceKind::Git if self.location.starts_with("file://") => true,
+            _ => Path::new(&self.location).is_absolute(),
+        };
+        let subdir = match &self.subdir {
+            Some(subdir) => {
+                safe_path(subdir).is_ok_and(|p| !p.as_os_str().is_empty())
+            }
+            None => true,
+        };
+        location && subdir
+    }
+
+    /// Saves source to the template in given directory
+    fn save(&self, dir: &Path) -> Result<(), Error> {
+        write(dir.join(SOURCE_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Installs template from the source to `dst`. Template is searched for
+    /// in the source root and then in the directory with the template name
+    fn install(&mut self, name: &str, dst: &Path) -> Result<(), Error> {
+        let checkout = match self.kind {
+            SourceKind::Path => None,
+            SourceKind::Git => Some(self.clone_repo(name)?),
+        };
+        let root = match &checkout {
+            Some(Checkout(dir)) => dir.clone(),
+            None => PathBuf::from(&self.location),
+        };
+        if !root.is_dir() {
+            return Err(SourceErr::NotFound(self.location.clone()).into());
+        }
+
+        if self.subdir.is_none()
+            && Template::read_manifest(&root)?.is_none()
+            && root.join(name).is_dir()
+        {
+            self.subdir = Some(name.to_string());
+        }
+        let src = match &self.subdir {
+            Some(subdir) => root.join(subdir),
+            None => root,
+        };
+        if Template::read_manifest(&src)?.is_none() {
+            return Err(SourceErr::NoManifest(self.location.clone()).into());
+        }
+
+        if dst.exists() {
+            remove_dir_all(dst)?;
+        }
+        create_dir_all(dst.join("template"))?;
+        for rel in dir_files(&src)? {
+            if rel.starts_with(".git") || rel == Path::new(SOURCE_FILE) {
+                continue;
+            }
+            let path = dst.join(&rel);
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)?;
+            }
+            copy(src.join(&rel), path)?;
+        }
+        self.save(dst)
+    }
+
+    /// Clones the git repository to a temporary directory and sets the
+    /// revision to its current commit
+    fn clone_repo(&mut self, name: &str) -> Result<Checkout, Error> {
+        let dir = std::env::temp_dir()
+            .join(format!("makeit-checkout-{}-{name}", process::id()));
+        _ = remove_dir_all(&dir);
+        let checkout = Checkout(dir);
+
+        let path = checkout.0.to_string_lossy();
+        git(&["clone", "--quiet", "--", &self.location, &path])?;
+        self.revision = Some(git(&["-C", &path, "rev-parse", "HEAD"])?);
+        Ok(checkout)
+    }
+}
+
+/// Adds template from the source given in args to the template directory
+/// from the config. Template name defaults to the source name
+pub fn add(config: &Config, args: &Args) -> Result<(), Error> {
+    let src = args.source.as_ref().ok_or(ArgsErr::MissingParam)?;
+    let mut source = Source::parse(src)?;
+    let name = args
+        .template
+        .clone()
+        .or_else(|| source.name())
+        .ok_or(ArgsErr::NoTemplate)?;
+    Template::check_name(&name)?;
+
+    let dst = config.template_dir.join(&name);
+    if dst.exists() && !replace_prompt(&name, args.yes) {
+        return Ok(());
+    }
+    source.install(&name, &dst)
+}
+
+/// Upgrades template given in args (or all templates in the template
+/// directory from the config, that were added from a source) by installing
+/// it again from its source
+pub fn upgrade(config: &Config, args: &Args) -> Result<(), Error> {
+    let tmplts = match &args.template {
+        Some(name) => {
+            let dir = config
+                .find_template(name)
+                .ok_or(TemplateErr::NotFound(name.to_string()))?;
+            if !dir.join(SOURCE_FILE).exists() {
+                return Err(SourceErr::NoSource(name.to_string()).into());
+            }
+            vec![(name.to_string(), dir)]
+        }
+        None => added_templates(&config.template_dir)?,
+    };
+
+    for (name, dir) in tmplts {
+        let Some(mut source) = Source::load(&dir)? else {
+            continue;
+        };
+        let old = source.revision.clone();
+        source.install(&name, &dir)?;
+
+        let state = match (old, &source.revision) {
+            (Some(old), Some(new)) if &old == new => {
+                "(up to date)".fg(Fg::Gray)
+            }
+            (Some(old), Some(new)) => {
+                format!("(upgraded {} -> {})", short(&old), short(new))
+                    .fg(Fg::Green)
+            }
+            _ => "(reinstalled)".fg(Fg::Green),
+        };
+        println!("{name} {state}");
+    }
+    Ok(())
+}
+
+/// Gets templates in given directory, which were added from a source
+fn added_templates(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut res = vec![];
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if path.join(SOURCE_FILE).exists() {
+            if let Some(name) = path.file_name() {
+                res.push((name.to_string_lossy().to_string(), path));
+            }
+        }
+    }
+    res.sort();
+    Ok(res)
+}
+
+/// Runs git with given arguments and returns its trimmed output
+fn git(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| SourceErr::Git(e.to_string()))?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(SourceErr::Git(err.trim().to_string()).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Shortens commit hash
+fn short(rev: &str) -> &str {
+    &rev[..rev.len().min(7)]
+}
@@ -1,40 +1,106 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::{
         copy, create_dir, create_dir_all, read_dir, read_to_string,
-        remove_dir_all, File,
+        remove_dir_all, rename, File,
     },
     io::{self, BufReader, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
+    rc::Rc,
 };
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use shell_words::split;
+use termint::{enums::fg::Fg, widgets::span::StrSpanExtension};
 use utf8_chars::BufReadCharsExt;
 
 use crate::{
+    archive::walk_dir,
     args::Args,
-    config::Config,
-    err::{error::Error, template_err::TemplateErr},
+    builtins::Builtins,
+    config::{Config, TemplateDir},
+    err::{
+        args_err::ArgsErr, error::Error, hook_err::HookErr,
+        template_err::TemplateErr,
+    },
     file_options::{FileAction, FileOptions},
-    parse::parser::Parser,
-    prompt::{not_empty_prompt, replace_prompt},
+    format::Format,
+    hooks::{run_command, Hooks, Step},
+    parse::{
+        ast::Value, delims::Delims, parser::Parser,
+        parser_options::ParserOptions,
+    },
+    prompt::{
+        hooks_prompt, not_empty_prompt, overwrite_prompt, replace_prompt,
+    },
+    trust::{manifest_hash, TrustStore},
+    vars::{VarLayer, Vars},
 };
 
 /// Represents makeit template
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Template {
+    #[serde(skip)]
+    name: String,
     #[serde(skip)]
     path: PathBuf,
-    #[serde(default)]
-    pre: Option<String>,
-    #[serde(default)]
-    post: Option<String>,
-    #[serde(default, rename = "fileOptions")]
+    #[serde(skip)]
+    partials_dirs: Vec<PathBuf>,
+    /// Directories of the templates this template inherits from, the
+    /// nearest parent first
+    #[serde(skip)]
+    inherited: Vec<PathBuf>,
+    #[serde(skip)]
+    format: Format,
+    /// Internal variables, which are computed when they're used
+    #[serde(skip)]
+    builtins: Option<Rc<Builtins>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extends: Option<String>,
+    /// Variables the generator positional arguments are assigned to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    args: Vec<String>,
+    /// Directory the generator renders to, relative to the project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pre: Hooks,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    post: Hooks,
+    #[serde(
+        default,
+        rename = "fileOptions",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
     file_options: HashMap<String, FileOptions>,
-    #[serde(default)]
-    vars: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    vars: HashMap<String, Value>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    computed: IndexMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delimiters: Option<Delims>,
+    #[serde(
+        default,
+        rename = "dropBlockLines",
+        skip_serializing_if = "Option::is_none"
+    )]
+    drop_block_lines: Option<bool>,
+    #[serde(
+        default,
+        rename = "stringVars",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    string_vars: bool,
+    #[serde(
+        default,
+        rename = "allowedEnv",
+        skip_serializing_if = "Option::is_none"
+    )]
+    allowed_env: Option<Vec<String>>,
 }
 
 impl Template {
@@ -43,6 +109,9 @@ impl Template {
         let template = args.template.as_ref().unwrap();
         let src = args.get_path();
 
+        if args.update {
+            return Template::update(config, template, &src, args.yes);
+        }
         let dir = config.template_dir.join(template);
         if dir.exists() {
             if !replace_prompt(template, args.yes) {
@@ -59,123 +128,782 @@ impl Template {
         let tmplt = Self {
             path: dir,
             vars: args.vars,
-            pre: args.pre,
-            post: args.post,
+            pre: args.pre.into(),
+            post: args.post.into(),
+            format: args.format.unwrap_or_default(),
             ..Self::default()
         };
         tmplt.save()
     }
 
+    /// Updates files of the template with given name from `src`, manifest
+    /// is kept and `fileOptions` entries that don't match any file are
+    /// reported
+    fn update(
+        config: &Config,
+        name: &str,
+        src: &Path,
+        yes: bool,
+    ) -> Result<(), Error> {
+        let Some(dir) = config.find_template(name) else {
+            return Err(TemplateErr::NotFound(name.to_string()).into());
+        };
+        let tmplt =
+            Template::open_dir(config, name, dir.clone(), &mut vec![])?;
+        if !replace_prompt(name, yes) {
+            return Ok(());
+        }
+
+        let dst = dir.join("template");
+        if dst.exists() {
+            remove_dir_all(&dst)?;
+        }
+        create_dir_all(&dst)?;
+        Template::copy_files_raw(src, &dst)?;
+
+        for path in tmplt.stale_options(&dst) {
+            println!(
+                "{}",
+                format!(
+                    "Warning: fileOptions entry '{path}' doesn't match any \
+                     file"
+                )
+                .fg(Fg::Yellow)
+            );
+        }
+        Ok(())
+    }
+
+    /// Gets `fileOptions` entries, which don't match any file in given
+    /// template files directory nor in the files of the parent templates
+    pub fn stale_options(&self, files: &Path) -> Vec<String> {
+        let mut res: Vec<_> = self
+            .file_options
+            .keys()
+            .filter(|path| {
+                !files.join(path).is_file()
+                    && !self
+                        .inherited
+                        .iter()
+                        .any(|d| d.join("template").join(path).is_file())
+            })
+            .cloned()
+            .collect();
+        res.sort();
+        res
+    }
+
     /// Loads template by given name
-    pub fn load(config: &Config, args: &Args) -> Result<(), Error> {
-        let template = args.template.as_ref().unwrap();
+    pub fn load(
+        config: &Config,
+        args: &Args,
+        mut vars: Vars,
+    ) -> Result<(), Error> {
+        if let Some((name, gen)) = args.generator() {
+            return Template::generate(config, args, name, gen, vars);
+        }
+
         let dst = args.get_path();
 
-        let dir = config.template_dir.join(template);
-        if !dir.exists() {
-            return Err(TemplateErr::NotFound(template.to_string()).into());
+        let mut tmplts = Template::open_all(config, args)?;
+        if dst.exists()
+            && dst.read_dir()?.next().is_some()
+            && !not_empty_prompt(args.yes)
+        {
+            return Ok(());
         }
+        Template::resolve_all(&mut tmplts, &mut vars)?;
+        Template::check_hooks(&mut tmplts, config, args)?;
 
-        if dst.exists() && dst.read_dir()?.next().is_some() {
-            if !not_empty_prompt(args.yes) {
-                return Ok(());
-            }
+        create_dir_all(&dst)?;
+        for tmplt in tmplts.iter_mut() {
+            tmplt.pre_exec(&dst)?;
+
+            tmplt.copy_files(&dst)?;
+
+            tmplt.post_exec(&dst)?;
         }
+        Ok(())
+    }
 
-        let path = dir.join("makeit.json");
-        let json = read_to_string(&path).unwrap_or(String::new());
-        let mut tmplt = serde_json::from_str::<Template>(&json)?;
-        tmplt.path = dir;
+    /// Runs generator `gen` of the template `name` in the project directory.
+    /// Positional arguments are assigned to the generator variables
+    fn generate(
+        config: &Config,
+        args: &Args,
+        name: &str,
+        gen: &str,
+        mut vars: Vars,
+    ) -> Result<(), Error> {
+        let tmplt = Template::open(config, name)?;
+        let mut gen = tmplt.open_generator(config, gen)?;
+        gen.string_vars |= args.string_vars;
 
-        for (name, value) in args.vars.iter() {
-            if !tmplt.vars.contains_key(name) {
-                tmplt.vars.insert(name.to_string(), value.to_string());
+        if args.gen_args.len() > gen.args.len() {
+            return Err(
+                TemplateErr::TooManyArgs(gen.name, gen.args.len()).into()
+            );
+        }
+        for (var, val) in gen.args.iter().zip(args.gen_args.iter()) {
+            vars.insert(VarLayer::Args, var, val.as_str());
+        }
+        let gens = std::slice::from_mut(&mut gen);
+        Template::resolve_all(gens, &mut vars)?;
+        Template::check_hooks(gens, config, args)?;
+
+        let mut dst = args.get_path();
+        if let Some(target) = &gen.target {
+            dst.push(gen.render(target, &gen.get_parser_opts(None))?);
+        }
+
+        let mut exists = false;
+        for (rel, src) in gen.files()? {
+            if src.is_file() {
+                let path = gen.file_dst(&rel, &dst.join(&rel))?;
+                exists |= path.is_some_and(|p| p.exists());
             }
         }
+        if exists && !overwrite_prompt(args.yes) {
+            return Ok(());
+        }
 
         create_dir_all(&dst)?;
-        tmplt.pre_exec(&dst)?;
+        gen.pre_exec(&dst)?;
+        gen.copy_files(&dst)?;
+        gen.post_exec(&dst)
+    }
 
-        let src = tmplt.get_template_dir();
-        tmplt.copy_files(&src, &dst)?;
+    /// Opens generator with given name, it's searched for in `generators`
+    /// directory of the template and of the templates it extends
+    fn open_generator(
+        &self,
+        config: &Config,
+        gen: &str,
+    ) -> Result<Self, Error> {
+        let name = format!("{}:{gen}", self.name);
+        let dirs: Vec<_> = [self.path.clone()]
+            .into_iter()
+            .chain(self.inherited.iter().cloned())
+            .collect();
+
+        let Some(path) = dirs
+            .iter()
+            .map(|d| d.join("generators").join(gen))
+            .find(|d| d.is_dir())
+        else {
+            return Err(TemplateErr::NotFound(name).into());
+        };
 
-        tmplt.post_exec(&dst)
+        let mut tmplt = Template::read_manifest(&path)?
+            .ok_or(TemplateErr::NoManifest(name.clone()))?;
+        tmplt.name = name;
+        tmplt.path = path;
+        tmplt.partials_dirs =
+            dirs.into_iter().chain(config.partials_dirs()).collect();
+        Ok(tmplt)
+    }
+
+    /// Gets names of the generators of the template in given directory
+    fn generators(dir: &Path) -> Result<Vec<String>, Error> {
+        let dir = dir.join("generators");
+        if !dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut res = vec![];
+        for entry in read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                res.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        res.sort();
+        Ok(res)
+    }
+
+    /// Prints final values of the template variables with the layer they
+    /// come from
+    pub fn show_vars(
+        config: &Config,
+        args: &Args,
+        mut vars: Vars,
+    ) -> Result<(), Error> {
+        let mut tmplts = Template::open_all(config, args)?;
+        Template::resolve_all(&mut tmplts, &mut vars)?;
+
+        for (name, (val, layer)) in vars.sources() {
+            println!(
+                "{} = {} {}",
+                name.fg(Fg::Green),
+                serde_json::to_string(&val)?,
+                format!("({layer})").fg(Fg::Gray)
+            );
+        }
+        Ok(())
     }
 
     /// Removes template
     pub fn remove(config: &Config, args: &Args) -> Result<(), Error> {
         let template = args.template.as_ref().unwrap();
-        let dir = config.template_dir.join(template);
-        if !dir.exists() {
+        let Some(dir) = config.find_template(template) else {
             return Err(TemplateErr::NotFound(template.to_string()).into());
-        }
+        };
 
         Ok(remove_dir_all(&dir)?)
     }
 
-    /// Lists all templates
+    /// Renames template given in args to the target name in args, template
+    /// stays in the same template directory. When the target is the same
+    /// directory (such as case-only rename on case-insensitive filesystem),
+    /// it's only renamed
+    pub fn rename(config: &Config, args: &Args) -> Result<(), Error> {
+        let (src, dst) = Template::src_dst(config, args, true)?;
+        if dst.exists() && !Template::same_path(&src, &dst) {
+            remove_dir_all(&dst)?;
+        }
+        Ok(rename(src, dst)?)
+    }
+
+    /// Copies template given in args to the template directory from the
+    /// config with the target name in args
+    pub fn copy(config: &Config, args: &Args) -> Result<(), Error> {
+        let (src, dst) = Template::src_dst(config, args, false)?;
+        if dst.exists() {
+            remove_dir_all(&dst)?;
+        }
+        create_dir_all(&dst)?;
+        Template::copy_files_raw(&src, &dst)
+    }
+
+    /// Lists all templates with the directory they come from, warns about
+    /// templates shadowed by the templates with the same name
     pub fn list(config: &Config) -> Result<(), Error> {
-        Template::list_tmplts(&config.template_dir)
+        let mut tmplts: BTreeMap<String, Vec<TemplateDir>> = BTreeMap::new();
+        for dir in config.template_dirs() {
+            for name in Template::list_tmplts(&dir.path)? {
+                tmplts.entry(name).or_default().push(dir.clone());
+            }
+        }
+
+        for (name, dirs) in tmplts.iter() {
+            let Some((used, shadowed)) = dirs.split_first() else {
+                continue;
+            };
+            println!("{name} {}", format!("({used})").fg(Fg::Gray));
+            let gens = Template::generators(&used.path.join(name))?;
+            if !gens.is_empty() {
+                println!(
+                    "  {}",
+                    format!("generators: {}", gens.join(", ")).fg(Fg::Gray)
+                );
+            }
+            for dir in shadowed {
+                println!(
+                    "  {} shadows template in ({dir})",
+                    "Warning:".fg(Fg::Yellow)
+                );
+            }
+        }
+        Ok(())
     }
 
-    /// Saves the template
+    /// Saves the template manifest in the template format
     fn save(&self) -> Result<(), Error> {
-        let path = self.path.join("makeit.json");
+        let path = self.path.join(self.format.manifest());
         let mut file = File::create(&path)?;
 
-        let json_string = serde_json::to_string_pretty(self)?;
-        file.write_all(json_string.as_bytes())?;
+        let content = self.format.serialize(self)?;
+        file.write_all(content.as_bytes())?;
         Ok(())
     }
 
-    /// Executes pre script
-    fn pre_exec(&self, dst: &PathBuf) -> Result<(), Error> {
-        let Some(pre) = &self.pre else {
-            return Ok(());
+    /// Opens template with given name
+    fn open(config: &Config, name: &str) -> Result<Self, Error> {
+        let Some(dir) = config.find_template(name) else {
+            return Err(TemplateErr::NotFound(name.to_string()).into());
         };
-        self.exec_script(pre, dst)
-            .map_err(|_| TemplateErr::PreExec.into())
+        Template::open_dir(config, name, dir, &mut vec![])
     }
 
-    /// Executes post script
-    fn post_exec(&self, dst: &PathBuf) -> Result<(), Error> {
-        let Some(post) = &self.post else {
-            return Ok(());
+    /// Opens template in given directory and the templates it extends,
+    /// `chain` contains names of the templates extending this one
+    fn open_dir(
+        config: &Config,
+        name: &str,
+        dir: PathBuf,
+        chain: &mut Vec<String>,
+    ) -> Result<Self, Error> {
+        let mut tmplt = Template::read_manifest(&dir)?
+            .ok_or(TemplateErr::NoManifest(name.to_string()))?;
+        tmplt.name = name.to_string();
+        tmplt.path = dir;
+        tmplt.partials_dirs = config.partials_dirs();
+
+        let Some(parent) = tmplt.extends.clone() else {
+            return Ok(tmplt);
+        };
+        chain.push(name.to_string());
+        if chain.contains(&parent) {
+            chain.push(parent);
+            return Err(TemplateErr::ExtendsCycle(chain.join(" -> ")).into());
+        }
+        let Some(dir) = config.find_template(&parent) else {
+            return Err(TemplateErr::NotFound(parent).into());
         };
-        self.exec_script(post, dst)
-            .map_err(|_| TemplateErr::PostExec.into())
+        let parent = Template::open_dir(config, &parent, dir, chain)?;
+        tmplt.inherit(parent);
+        Ok(tmplt)
     }
 
-    /// Gets template directory path
-    fn get_template_dir(&self) -> PathBuf {
-        self.path.join("template")
+    /// Inherits settings from the parent template. Variables, computed
+    /// variables and file options are merged, the other settings are
+    /// inherited when not set
+    fn inherit(&mut self, parent: Template) {
+        let mut vars = parent.vars;
+        vars.extend(self.vars.drain());
+        self.vars = vars;
+
+        let mut computed = parent.computed;
+        computed.extend(self.computed.drain(..));
+        self.computed = computed;
+
+        let mut file_options = parent.file_options;
+        file_options.extend(self.file_options.drain());
+        self.file_options = file_options;
+
+        let mut deps = parent.dependencies;
+        for dep in self.dependencies.drain(..) {
+            if !deps.contains(&dep) {
+                deps.push(dep);
+            }
+        }
+        self.dependencies = deps;
+
+        if self.pre.is_empty() {
+            self.pre = parent.pre;
+        }
+        if self.post.is_empty() {
+            self.post = parent.post;
+        }
+        self.delimiters = self.delimiters.take().or(parent.delimiters);
+        self.drop_block_lines =
+            self.drop_block_lines.or(parent.drop_block_lines);
+        self.allowed_env = self.allowed_env.take().or(parent.allowed_env);
+        self.string_vars |= parent.string_vars;
+
+        self.inherited =
+            [parent.path].into_iter().chain(parent.inherited).collect();
     }
 
-    /// Copies files recursively
-    fn copy_files(
-        &mut self,
-        src: &PathBuf,
-        dst: &PathBuf,
+    /// Reads and checks manifest of the template in given directory, the
+    /// first existing manifest is used. Returns [`None`] without manifest
+    pub fn read_manifest(dir: &Path) -> Result<Option<Self>, Error> {
+        let Some(format) = Format::ALL
+            .into_iter()
+            .find(|f| dir.join(f.manifest()).exists())
+        else {
+            return Ok(None);
+        };
+
+        let content = read_to_string(dir.join(format.manifest()))?;
+        Template::parse_manifest(format, &content).map(Some)
+    }
+
+    /// Parses template manifest in given format and checks its validity
+    pub fn parse_manifest(
+        format: Format,
+        content: &str,
+    ) -> Result<Self, Error> {
+        let mut tmplt = format.deserialize::<Template>(content)?;
+        tmplt.format = format;
+        tmplt.check_delims()?;
+        Ok(tmplt)
+    }
+
+    /// Adds template variables to given layered variables and replaces
+    /// template variables with their final values
+    fn resolve_vars(&mut self, vars: &mut Vars) -> Result<(), TemplateErr> {
+        self.vars = vars.resolve();
+        self.builtins = vars.builtins();
+        self.stringify_vars();
+        self.compute_vars(vars)?;
+        self.stringify_vars();
+        Ok(())
+    }
+
+    /// Opens templates given in args with their dependencies. Dependencies
+    /// are placed before the template and each template is opened once
+    fn open_all(config: &Config, args: &Args) -> Result<Vec<Self>, Error> {
+        let mut tmplts = vec![];
+        for name in args.templates() {
+            Template::open_deps(config, name, &mut vec![], &mut tmplts)?;
+        }
+        for tmplt in tmplts.iter_mut() {
+            tmplt.string_vars |= args.string_vars;
+        }
+        Ok(tmplts)
+    }
+
+    /// Opens template with given name and its dependencies, `chain`
+    /// contains names of templates depending on this one
+    fn open_deps(
+        config: &Config,
+        name: &str,
+        chain: &mut Vec<String>,
+        tmplts: &mut Vec<Self>,
     ) -> Result<(), Error> {
-        for entry in read_dir(src)? {
-            let path = entry?.path();
-            let Some(filename) = path.file_name() else {
+        if chain.iter().any(|n| n == name) {
+            chain.push(name.to_string());
+            return Err(
+                TemplateErr::DependencyCycle(chain.join(" -> ")).into()
+            );
+        }
+        if tmplts.iter().any(|t| t.name == name) {
+            return Ok(());
+        }
+
+        let tmplt = Template::open(config, name)?;
+        chain.push(name.to_string());
+        for dep in tmplt.dependencies.iter() {
+            Template::open_deps(config, dep, chain, tmplts)?;
+        }
+        chain.pop();
+        tmplts.push(tmplt);
+        Ok(())
+    }
+
+    /// Resolves variables of all the templates in one shared context.
+    /// Template variables of later templates take precedence and computed
+    /// variables are evaluated in the template order
+    fn resolve_all(
+        tmplts: &mut [Self],
+        vars: &mut Vars,
+    ) -> Result<(), TemplateErr> {
+        for tmplt in tmplts.iter_mut() {
+            vars.extend(VarLayer::Template, tmplt.vars.drain());
+        }
+        for tmplt in tmplts.iter_mut() {
+            tmplt.resolve_vars(vars)?;
+        }
+        for tmplt in tmplts.iter_mut() {
+            tmplt.vars = vars.resolve();
+            tmplt.stringify_vars();
+        }
+        Ok(())
+    }
+
+    /// Converts all variables to strings when string variables are enabled
+    fn stringify_vars(&mut self) {
+        if self.string_vars {
+            self.vars = self
+                .vars
+                .drain()
+                .filter_map(|(name, val)| Some((name, val.to_compat()?)))
+                .collect();
+        }
+    }
+
+    /// Evaluates computed variables in the order they are defined
+    fn compute_vars(&mut self, vars: &mut Vars) -> Result<(), TemplateErr> {
+        let opts = self.get_parser_opts(None);
+        for (name, expr) in self.computed.iter() {
+            let mut text = expr.chars().map(Ok);
+            let val =
+                Parser::eval(&mut text, &self.vars, &opts).map_err(|e| {
+                    TemplateErr::Computed(name.to_string(), Box::new(e))
+                })?;
+
+            if val != Value::Null {
+                vars.insert(VarLayer::Computed, name, val.clone());
+                self.vars.insert(name.to_string(), val);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether all the delimiters in the template are valid
+    fn check_delims(&self) -> Result<(), TemplateErr> {
+        let valid = self
+            .delimiters
+            .iter()
+            .chain(self.file_options.values().flat_map(|o| &o.delimiters))
+            .all(|d| d.is_valid());
+        if valid {
+            Ok(())
+        } else {
+            Err(TemplateErr::InvalidDelims)
+        }
+    }
+
+    /// Gets parser options of the file, falls back to template options
+    fn get_parser_opts(&self, item: Option<&FileOptions>) -> ParserOptions {
+        let delims = item
+            .and_then(|i| i.delimiters.as_ref())
+            .or(self.delimiters.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        let drop_block_lines = item
+            .and_then(|i| i.drop_block_lines)
+            .or(self.drop_block_lines)
+            .unwrap_or_default();
+
+        ParserOptions {
+            delims,
+            drop_block_lines,
+            include_dirs: [self.path.clone()]
+                .into_iter()
+                .chain(self.inherited.iter().cloned())
+                .chain(self.partials_dirs.iter().cloned())
+                .collect(),
+            include_chain: vec![],
+            allowed_env: self.allowed_env.clone(),
+            env: None,
+            builtins: self.builtins.clone(),
+            string_vars: self.string_vars,
+        }
+    }
+
+    /// Asks whether to run hooks of the templates, which aren't trusted
+    /// (they weren't confirmed or their manifests changed since). Hooks are
+    /// skipped when it's declined or with `--no-scripts`. With `--yes` and
+    /// without `--trust-scripts`, untrusted hooks are an error. Only hooks
+    /// confirmed in the prompt are trusted
+    fn check_hooks(
+        tmplts: &mut [Self],
+        config: &Config,
+        args: &Args,
+    ) -> Result<(), Error> {
+        let has_hooks = |t: &Template| !t.pre.is_empty() || !t.post.is_empty();
+        let mut store = TrustStore::load(config)?;
+        let mut untrusted = vec![];
+        for (i, tmplt) in tmplts.iter().enumerate() {
+            if !has_hooks(tmplt) {
                 continue;
+            }
+            let hash = manifest_hash(&tmplt.manifest_dirs())?;
+            if args.no_scripts || !store.is_trusted(&tmplt.name, &hash) {
+                untrusted.push((i, hash));
+            }
+        }
+        if untrusted.is_empty() {
+            return Ok(());
+        }
+
+        if !args.no_scripts && args.yes && !args.trust_scripts {
+            let (i, _) = &untrusted[0];
+            let name = tmplts[*i].name.clone();
+            return Err(TemplateErr::UntrustedHooks(name).into());
+        }
+
+        if !args.no_scripts {
+            for (i, _) in untrusted.iter() {
+                tmplts[*i].print_hooks();
+            }
+            if hooks_prompt(args.trust_scripts) {
+                if !args.trust_scripts {
+                    for (i, hash) in untrusted {
+                        store.trust(&tmplts[i].name, hash);
+                    }
+                    store.save()?;
+                }
+                return Ok(());
+            }
+        }
+
+        for (i, _) in untrusted {
+            let tmplt = &mut tmplts[i];
+            println!(
+                "{}",
+                format!("Skipping hooks of '{}'", tmplt.name).fg(Fg::Gray)
+            );
+            tmplt.pre = Hooks::default();
+            tmplt.post = Hooks::default();
+        }
+        Ok(())
+    }
+
+    /// Prints rendered commands of the template hooks
+    fn print_hooks(&self) {
+        println!(
+            "Template '{}' runs these commands:",
+            self.name.fg(Fg::Yellow)
+        );
+        let opts = self.get_parser_opts(None);
+        for (hook, hooks) in [("pre", &self.pre), ("post", &self.post)] {
+            for step in hooks.0.iter() {
+                let cmd = self
+                    .render(&step.command, &opts)
+                    .unwrap_or(step.command.clone());
+                let mut notes = vec![];
+                if let Some(cwd) = &step.cwd {
+                    notes.push(format!("in {cwd}"));
+                }
+                if step.shell {
+                    notes.push("shell".to_string());
+                }
+                if let Some(when) = &step.when {
+                    notes.push(format!("when {when}"));
+                }
+
+                print!("  {} {cmd}", format!("{hook}:").fg(Fg::Gray));
+                if !notes.is_empty() {
+                    let notes = format!("({})", notes.join(", "));
+                    print!(" {}", notes.fg(Fg::Gray));
+                }
+                println!();
+            }
+        }
+    }
+
+    /// Gets directories of the template and the templates it extends
+    fn manifest_dirs(&self) -> Vec<PathBuf> {
+        [self.path.clone()]
+            .into_iter()
+            .chain(self.inherited.iter().cloned())
+            .collect()
+    }
+
+    /// Executes pre hook
+    fn pre_exec(&self, dst: &Path) -> Result<(), Error> {
+        self.exec_hooks(&self.pre, "pre", dst)
+    }
+
+    /// Executes post hook
+    fn post_exec(&self, dst: &Path) -> Result<(), Error> {
+        self.exec_hooks(&self.post, "post", dst)
+    }
+
+    /// Executes steps of the hook in order. Failure of the step with
+    /// `continueOnError` is only reported
+    fn exec_hooks(
+        &self,
+        hooks: &Hooks,
+        hook: &str,
+        dst: &Path,
+    ) -> Result<(), Error> {
+        for (i, step) in hooks.0.iter().enumerate() {
+            let desc = format!("{hook} hook step {}", i + 1);
+            match self.exec_step(step, &desc, dst) {
+                Err(e) if step.continue_on_error => {
+                    println!("{} {e}", "Warning:".fg(Fg::Yellow))
+                }
+                res => res?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes hook step in `dst` (or in its working directory relative to
+    /// `dst`), when its condition is truthy
+    fn exec_step(
+        &self,
+        step: &Step,
+        desc: &str,
+        dst: &Path,
+    ) -> Result<(), HookErr> {
+        let opts = self.get_parser_opts(None);
+        let expr_err = |e| HookErr::Expr(desc.to_string(), Box::new(e));
+        if let Some(when) = &step.when {
+            let val =
+                Parser::eval(&mut when.chars().map(Ok), &self.vars, &opts)
+                    .map_err(expr_err)?;
+            if !val.is_truthy() {
+                return Ok(());
+            }
+        }
+
+        let cmd = self.render(&step.command, &opts).map_err(expr_err)?;
+        let mut cwd = dst.to_owned();
+        if let Some(dir) = &step.cwd {
+            cwd.push(self.render(dir, &opts).map_err(expr_err)?);
+        }
+
+        let mut command = if step.shell {
+            let (shell, flag) = if cfg!(windows) {
+                ("cmd", "/C")
+            } else {
+                ("sh", "-c")
+            };
+            let mut command = Command::new(shell);
+            command.args([flag, &cmd]);
+            command
+        } else {
+            let args = split(&cmd).map_err(|e| {
+                HookErr::InvalidCommand(desc.to_string(), e.to_string())
+            })?;
+            let Some((prog, args)) = args.split_first() else {
+                return Ok(());
             };
+            let mut command = Command::new(prog);
+            command.args(args);
+            command
+        };
 
-            let dst_path = dst.join(filename);
-            if path.is_dir() {
+        let builtins = self.builtins.iter().flat_map(|b| b.vars());
+        command
+            .current_dir(cwd)
+            .envs(builtins.map(|(k, v)| (k, v.to_string())))
+            .envs(self.vars.iter().map(|(k, v)| (k.clone(), v.to_string())));
+        let (status, tail) = run_command(&mut command, step.quiet)
+            .map_err(|e| HookErr::Spawn(desc.to_string(), cmd.clone(), e))?;
+        if !status.success() {
+            return Err(HookErr::Failed(
+                desc.to_string(),
+                cmd,
+                status.code(),
+                tail,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Renders string containing code blocks using template variables
+    fn render(
+        &self,
+        text: &str,
+        opts: &ParserOptions,
+    ) -> Result<String, Error> {
+        let mut res = String::new();
+        Parser::string(&mut text.chars().map(Ok), &self.vars, &mut res, opts)?;
+        Ok(res)
+    }
+
+    /// Gets template directory path
+    fn get_template_dir(&self) -> PathBuf {
+        self.path.join("template")
+    }
+
+    /// Copies files of the template and the templates it extends to `dst`
+    fn copy_files(&self, dst: &Path) -> Result<(), Error> {
+        for (rel, src) in self.files()? {
+            let dst_path = dst.join(&rel);
+            if src.is_dir() {
                 Template::create_dir(&dst_path)?;
-                self.copy_files(&path, &dst_path)?;
             } else {
-                self.make_file(&path, &dst_path)?;
+                self.make_file(&rel, &src, &dst_path)?;
             }
         }
         Ok(())
     }
 
+    /// Gets files and directories of the template (relative to the template
+    /// files directory) with their source path. Files of the template
+    /// override files of the templates it extends
+    fn files(&self) -> Result<BTreeMap<PathBuf, PathBuf>, Error> {
+        let mut res = BTreeMap::new();
+        let parents = self.inherited.iter().rev().map(|d| d.join("template"));
+        for root in parents.chain([self.get_template_dir()]) {
+            if root.is_dir() {
+                walk_dir(&root, &mut |rel, path| {
+                    res.insert(rel.to_path_buf(), path.to_path_buf());
+                })?;
+            }
+        }
+        Ok(res)
+    }
+
     /// Copies files raw - without parsing
-    fn copy_files_raw(src: &PathBuf, dst: &PathBuf) -> Result<(), Error> {
+    fn copy_files_raw(src: &Path, dst: &Path) -> Result<(), Error> {
         for entry in read_dir(src)? {
             let path = entry?.path();
             let Some(filename) = path.file_name() else {
@@ -193,7 +921,62 @@ impl Template {
         Ok(())
     }
 
-    fn list_tmplts(dir: &PathBuf) -> Result<(), Error> {
+    /// Checks whether the template name is valid, name has to be single
+    /// path component other than the shared partials directory
+    pub fn check_name(name: &str) -> Result<(), TemplateErr> {
+        if Path::new(name).file_name() != Some(name.as_ref())
+            || name == "partials"
+        {
+            return Err(TemplateErr::InvalidName(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Gets source and destination directory of renamed or copied template,
+    /// asks whether to replace existing destination and fails when it's
+    /// declined. Copying template to itself fails
+    fn src_dst(
+        config: &Config,
+        args: &Args,
+        same_dir: bool,
+    ) -> Result<(PathBuf, PathBuf), Error> {
+        let name = args.template.as_ref().ok_or(ArgsErr::NoTemplate)?;
+        let target = args.target.as_ref().ok_or(ArgsErr::MissingParam)?;
+        Template::check_name(target)?;
+
+        let Some(src) = config.find_template(name) else {
+            return Err(TemplateErr::NotFound(name.to_string()).into());
+        };
+        let dst = match (same_dir, src.parent()) {
+            (true, Some(parent)) => parent.join(target),
+            _ => config.template_dir.join(target),
+        };
+        let same = Template::same_path(&src, &dst);
+        if dst == src || (same && !same_dir) {
+            return Err(TemplateErr::Exists(target.to_string()).into());
+        }
+        if dst.exists() && !same && !replace_prompt(target, args.yes) {
+            return Err(TemplateErr::Exists(target.to_string()).into());
+        }
+        Ok((src, dst))
+    }
+
+    /// Checks whether both paths exist and point to the same location
+    fn same_path(a: &Path, b: &Path) -> bool {
+        match (a.canonicalize(), b.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Gets names of the templates in given directory, partials directory
+    /// is skipped and missing directory contains no templates
+    fn list_tmplts(dir: &Path) -> Result<Vec<String>, Error> {
+        if !dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut res = vec![];
         for entry in read_dir(dir)? {
             let entry = entry?;
             let file_type = entry.file_type()?;
@@ -203,85 +986,80 @@ impl Template {
                 continue;
             };
 
-            if file_type.is_dir() {
-                println!("{}", filename.to_str().unwrap_or(""));
+            if file_type.is_dir() && filename != "partials" {
+                res.push(filename.to_string_lossy().to_string());
             }
         }
-        Ok(())
+        Ok(res)
     }
 
-    /// Executes script
-    fn exec_script(
+    /// Makes file - follows options stored in template config
+    fn make_file(
         &self,
-        script: &String,
-        dst: &PathBuf,
-    ) -> Result<(), String> {
-        let mut pcmd = String::new();
-        Parser::string(&mut script.chars().map(Ok), &self.vars, &mut pcmd)
-            .map_err(|e| e.to_string())?;
-
-        let args = split(&pcmd).map_err(|e| e.to_string())?;
-        if args.is_empty() {
+        rel: &Path,
+        src: &Path,
+        dst: &Path,
+    ) -> Result<(), Error> {
+        let item = self.file_options.get(&*rel.to_string_lossy());
+        let Some(dst) = self.file_dst(rel, dst)? else {
             return Ok(());
-        }
-
-        let cmd = &args[0];
-        let args = &args[1..];
+        };
 
-        let command = Command::new(cmd)
-            .args(args)
-            .current_dir(dst)
-            .envs(&self.vars)
-            .output()
-            .map_err(|e| e.to_string())?;
-        if !command.status.success() {
-            return Err(String::from_utf8_lossy(&command.stderr).to_string());
+        match item {
+            Some(item) if item.action == FileAction::Make => {
+                self.parse_file(src, &dst, &self.get_parser_opts(Some(item)))
+            }
+            _ => Template::copy_file(src, &dst),
         }
-        Ok(())
     }
 
-    /// Makes file - follows options stored in template config
-    fn make_file(&self, src: &PathBuf, dst: &PathBuf) -> Result<(), Error> {
-        let rel_path = src
-            .strip_prefix(self.get_template_dir())
-            .map(|p| p.to_path_buf())
-            .map_err(|e| e.to_string())?;
-        let path = rel_path.to_string_lossy().to_string();
-        let item = match self.file_options.get(&path) {
-            Some(i) => i,
-            None => return Template::copy_file(src, dst),
+    /// Gets path the file is made to, its name can be changed by the file
+    /// options. Returns [`None`] when the file is ignored
+    fn file_dst(
+        &self,
+        rel: &Path,
+        dst: &Path,
+    ) -> Result<Option<PathBuf>, Error> {
+        let Some(item) = self.file_options.get(&*rel.to_string_lossy()) else {
+            return Ok(Some(dst.to_owned()));
         };
+        if item.action == FileAction::Ignore {
+            return Ok(None);
+        }
 
         let mut dst = dst.to_owned();
         if let Some(name) = &item.name {
-            let mut filename = String::new();
-            let mut iter = name.chars().map(Ok);
-            Parser::string(&mut iter, &self.vars, &mut filename)?;
-            dst.set_file_name(filename);
-        }
-
-        match &item.action {
-            FileAction::Copy => Template::copy_file(src, &dst),
-            FileAction::Make => self.parse_file(src, &dst),
-            FileAction::Ignore => Ok(()),
+            dst.set_file_name(
+                self.render(name, &self.get_parser_opts(Some(item)))?,
+            );
         }
+        Ok(Some(dst))
     }
 
     /// Copies file from `src` to `dst` without parsing it
-    fn copy_file(src: &PathBuf, dst: &PathBuf) -> Result<(), Error> {
+    fn copy_file(src: &Path, dst: &Path) -> Result<(), Error> {
         copy(src, dst)?;
         Ok(())
     }
 
     /// Copies file from `src` to `dst` with parsing it
-    fn parse_file(&self, src: &PathBuf, dst: &PathBuf) -> Result<(), Error> {
+    fn parse_file(
+        &self,
+        src: &Path,
+        dst: &Path,
+        opts: &ParserOptions,
+    ) -> Result<(), Error> {
+        let opts = ParserOptions {
+            include_chain: vec![src.canonicalize()?],
+            ..opts.clone()
+        };
         let mut buf = BufReader::new(File::open(src)?);
         let mut chars = buf.chars();
-        Parser::file(&mut chars, &self.vars, dst)
+        Parser::file(&mut chars, &self.vars, dst, &opts)
     }
 
     /// Creates dir when doesn't exist
-    fn create_dir(path: &PathBuf) -> io::Result<()> {
+    fn create_dir(path: &Path) -> io::Result<()> {
         match create_dir(path) {
             Ok(()) => Ok(()),
             Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(()),
@@ -289,15 +1067,3 @@ impl Template {
         }
     }
 }
-
-impl Default for Template {
-    fn default() -> Self {
-        Self {
-            path: Default::default(),
-            pre: Default::default(),
-            post: Default::default(),
-            file_options: Default::default(),
-            vars: Default::default(),
-        }
-    }
-}
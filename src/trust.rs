@@ -0,0 +1,79 @@
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, read, read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{config::Config, err::error::Error, format::Format};
+
+/// File with the trusted templates, stored next to the config file
+pub const TRUST_FILE: &str = "trusted.json";
+
+/// Templates whose hooks were confirmed by the user. Template name is mapped
+/// to the hash of its manifests, so the template isn't trusted after its
+/// manifest changes
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(flatten)]
+    templates: BTreeMap<String, String>,
+}
+
+impl TrustStore {
+    /// Loads trust store belonging to the given config, missing store is
+    /// empty
+    pub fn load(config: &Config) -> Result<Self, Error> {
+        let path = config
+            .path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(TRUST_FILE);
+        let mut store = if path.exists() {
+            serde_json::from_str(&read_to_string(&path)?)?
+        } else {
+            TrustStore::default()
+        };
+        store.path = path;
+        Ok(store)
+    }
+
+    /// Checks whether template with given manifest hash is trusted
+    pub fn is_trusted(&self, name: &str, hash: &str) -> bool {
+        self.templates.get(name).is_some_and(|h| h == hash)
+    }
+
+    /// Trusts template with given manifest hash
+    pub fn trust(&mut self, name: &str, hash: String) {
+        self.templates.insert(name.to_string(), hash);
+    }
+
+    /// Saves the trust store
+    pub fn save(&self) -> Result<(), Error> {
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir)?;
+        }
+        write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Gets hash of the manifests of the templates in given directories
+pub fn manifest_hash(dirs: &[PathBuf]) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    for dir in dirs {
+        let manifest = Format::ALL
+            .iter()
+            .map(|f| dir.join(f.manifest()))
+            .find(|p| p.exists());
+        if let Some(manifest) = manifest {
+            let content = read(manifest)?;
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(content);
+        }
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
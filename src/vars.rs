@@ -0,0 +1,134 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Display,
+    fs::read_to_string,
+    path::Path,
+    rc::Rc,
+};
+
+use crate::{
+    builtins::Builtins, err::error::Error, format::Format, parse::ast::Value,
+};
+
+/// Prefix of the environment variables, which are used as template variables
+pub const ENV_PREFIX: &str = "MAKEIT_VAR_";
+
+/// Represents layer the variable is defined in. Variables in later layers
+/// take precedence over the variables in the earlier ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VarLayer {
+    /// Internal variables (starting with `_`)
+    Builtin,
+    /// Global user variables from makeit config
+    Global,
+    /// Default values from template config (`makeit.json`)
+    Template,
+    /// Variables from the answers file (`--vars-file`)
+    Answers,
+    /// Environment variables prefixed with [`ENV_PREFIX`]
+    Env,
+    /// Variables defined with `-D` argument
+    Args,
+    /// Variables computed in `computed` section of template config
+    Computed,
+}
+
+impl Display for VarLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VarLayer::Builtin => write!(f, "built-in"),
+            VarLayer::Global => write!(f, "global"),
+            VarLayer::Template => write!(f, "template"),
+            VarLayer::Answers => write!(f, "answers file"),
+            VarLayer::Env => write!(f, "environment"),
+            VarLayer::Args => write!(f, "command line"),
+            VarLayer::Computed => write!(f, "computed"),
+        }
+    }
+}
+
+/// Variables composed of multiple layers
+#[derive(Debug, Default)]
+pub struct Vars {
+    layers: BTreeMap<VarLayer, HashMap<String, Value>>,
+    builtins: Option<Rc<Builtins>>,
+}
+
+impl Vars {
+    /// Inserts variable to the given layer
+    pub fn insert<T, V>(&mut self, layer: VarLayer, name: T, value: V)
+    where
+        T: AsRef<str>,
+        V: Into<Value>,
+    {
+        self.layers
+            .entry(layer)
+            .or_default()
+            .insert(name.as_ref().to_string(), value.into());
+    }
+
+    /// Extends given layer with given variables
+    pub fn extend<T>(&mut self, layer: VarLayer, vars: T)
+    where
+        T: IntoIterator<Item = (String, Value)>,
+    {
+        self.layers.entry(layer).or_default().extend(vars);
+    }
+
+    /// Sets internal variables, they are part of the [`VarLayer::Builtin`]
+    /// layer and they are computed only when they're looked up
+    pub fn set_builtins(&mut self, builtins: Builtins) {
+        self.builtins = Some(Rc::new(builtins));
+    }
+
+    /// Gets internal variables
+    pub fn builtins(&self) -> Option<Rc<Builtins>> {
+        self.builtins.clone()
+    }
+
+    /// Gets variables from the environment, prefix is stripped from names
+    pub fn env() -> HashMap<String, Value> {
+        env::vars()
+            .filter_map(|(name, val)| {
+                let name = name.strip_prefix(ENV_PREFIX)?;
+                Some((name.to_string(), Value::String(val)))
+            })
+            .filter(|(name, _)| !name.is_empty())
+            .collect()
+    }
+
+    /// Loads variables from the answers file. Format is chosen based on the
+    /// file extension (`toml`, `yaml`, `yml`, otherwise JSON)
+    pub fn file(path: &Path) -> Result<HashMap<String, Value>, Error> {
+        let content = read_to_string(path)?;
+        Format::from_path(path).deserialize(&content)
+    }
+
+    /// Gets final values of the variables, internal variables set by
+    /// [`Vars::set_builtins`] aren't included
+    pub fn resolve(&self) -> HashMap<String, Value> {
+        let mut res = HashMap::new();
+        for vars in self.layers.values() {
+            res.extend(vars.iter().map(|(n, v)| (n.clone(), v.clone())));
+        }
+        res
+    }
+
+    /// Gets final values of all the variables (computes all the internal
+    /// variables) with the layer they come from, sorted by the variable name
+    pub fn sources(&self) -> BTreeMap<String, (Value, VarLayer)> {
+        let mut res = BTreeMap::new();
+        if let Some(builtins) = &self.builtins {
+            for (name, val) in builtins.vars() {
+                res.insert(name, (val, VarLayer::Builtin));
+            }
+        }
+        for (layer, vars) in self.layers.iter() {
+            for (name, val) in vars.iter() {
+                res.insert(name.clone(), (val.clone(), *layer));
+            }
+        }
+        res
+    }
+}
@@ -15,8 +15,14 @@ impl<'a> Writer<'a> {
     pub fn write(&mut self, content: char) -> io::Result<()> {
         match self {
             Writer::File(writer) => writer.write_all(&[content as u8]),
-            Writer::String(string) => Ok(string.push(content)),
-            Writer::Stdout => Ok(print!("{content}")),
+            Writer::String(string) => {
+                string.push(content);
+                Ok(())
+            }
+            Writer::Stdout => {
+                print!("{content}");
+                Ok(())
+            }
         }
     }
 
@@ -24,8 +30,14 @@ impl<'a> Writer<'a> {
     pub fn write_str(&mut self, content: &str) -> io::Result<()> {
         match self {
             Writer::File(writer) => writer.write_all(content.as_bytes()),
-            Writer::String(string) => Ok(string.push_str(content)),
-            Writer::Stdout => Ok(print!("{content}")),
+            Writer::String(string) => {
+                string.push_str(content);
+                Ok(())
+            }
+            Writer::Stdout => {
+                print!("{content}");
+                Ok(())
+            }
         }
     }
 }
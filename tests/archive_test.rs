@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, read_to_string, remove_dir_all, write, File},
+        io::Write,
+        path::Path,
+    };
+
+    use makeit::{
+        archive::{export, import, ArchiveFormat},
+        args::Args,
+        config::Config,
+        source::SOURCE_FILE,
+    };
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn config(dir: &Path) -> Config {
+        Config {
+            template_dir: dir.join("templates"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = temp_dir().join("makeit-archive-trip");
+        _ = remove_dir_all(&dir);
+        let config = config(&dir);
+
+        let src = config.template_dir.join("app");
+        create_dir_all(src.join("template").join("src")).unwrap();
+        write(src.join("makeit.json"), r#"{ "vars": { "a": 1 } }"#).unwrap();
+        write(src.join("template").join("src").join("main.c"), "main")
+            .unwrap();
+
+        for file in ["app.tar.gz", "app.zip"] {
+            let archive = dir.join(file);
+            let args = Args {
+                template: Some("app".to_string()),
+                ..Default::default()
+            };
+            export(&config, &args, &archive).unwrap();
+
+            let args = Args {
+                template: Some("copy".to_string()),
+                yes: true,
+                ..Default::default()
+            };
+            import(&config, &args, &archive).unwrap();
+
+            let dst = config.template_dir.join("copy");
+            assert_eq!(
+                read_to_string(dst.join("makeit.json")).unwrap(),
+                r#"{ "vars": { "a": 1 } }"#
+            );
+            assert_eq!(
+                read_to_string(dst.join("template/src/main.c")).unwrap(),
+                "main"
+            );
+        }
+    }
+
+    #[test]
+    fn unsafe_archive() {
+        let dir = temp_dir().join("makeit-archive-unsafe");
+        _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        let config = config(&dir);
+        let args = Args {
+            yes: true,
+            ..Default::default()
+        };
+
+        let write_zip = |name: &str, files: &[(&str, &str)]| {
+            let path = dir.join(name);
+            let mut zip = ZipWriter::new(File::create(&path).unwrap());
+            for (name, content) in files {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+            path
+        };
+
+        let evil =
+            write_zip("evil.zip", &[("makeit.json", "{}"), ("a/../../x", "")]);
+        assert!(import(&config, &args, &evil).is_err());
+        let abs = write_zip("abs.zip", &[("makeit.json", "{}"), ("/x", "")]);
+        assert!(import(&config, &args, &abs).is_err());
+        let invalid = write_zip("invalid.zip", &[("makeit.json", "{")]);
+        assert!(import(&config, &args, &invalid).is_err());
+        let missing = write_zip("missing.zip", &[("template/a", "")]);
+        assert!(import(&config, &args, &missing).is_err());
+        assert!(!config.template_dir.exists());
+
+        let nested = write_zip(
+            "nested.zip",
+            &[("app/makeit.toml", "pre = 'ls'"), ("app/template/a", "")],
+        );
+        import(&config, &args, &nested).unwrap();
+        assert!(config.template_dir.join("app/template/a").exists());
+
+        let source = write_zip(
+            "source.zip",
+            &[("makeit.json", "{}"), (SOURCE_FILE, r#"{ "kind": "git" }"#)],
+        );
+        import(&config, &args, &source).unwrap();
+        assert!(config.template_dir.join("source/makeit.json").exists());
+        assert!(!config
+            .template_dir
+            .join("source")
+            .join(SOURCE_FILE)
+            .exists());
+
+        let args = Args {
+            template: Some("../x".to_string()),
+            yes: true,
+            ..Default::default()
+        };
+        assert!(import(&config, &args, &nested).is_err());
+        assert!(!dir.join("x").exists());
+
+        assert!(ArchiveFormat::from_path(Path::new("a.rar")).is_err());
+    }
+}
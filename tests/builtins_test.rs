@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        env::{self, temp_dir},
+        fs::{create_dir_all, write},
+        rc::Rc,
+    };
+
+    use chrono::{Datelike, Local};
+    use makeit::{
+        builtins::Builtins,
+        parse::{ast::Value, parser::Parser, parser_options::ParserOptions},
+    };
+
+    #[test]
+    fn project_vars() {
+        let dst = temp_dir().join("makeit-builtins").join("project");
+        let builtins = Builtins::new(&dst, Some("rust".into()), None).unwrap();
+
+        assert_eq!(builtins.get("_PNAME"), Some(Value::from("project")));
+        assert_eq!(builtins.get("_TEMPLATE"), Some(Value::from("rust")));
+        assert_eq!(
+            builtins.get("_MAKEIT_VERSION"),
+            Some(Value::from(env!("CARGO_PKG_VERSION")))
+        );
+        assert_eq!(builtins.get("_UNKNOWN"), None);
+
+        let builtins = Builtins::new(&dst, None, None).unwrap();
+        assert!(!builtins.vars().contains_key("_TEMPLATE"));
+    }
+
+    #[test]
+    fn date_format() {
+        let dst = temp_dir();
+        let year = Local::now().year();
+
+        let builtins = Builtins::new(&dst, None, Some("%Y")).unwrap();
+        assert_eq!(builtins.get("_YEAR"), Some(Value::Number(year as f64)));
+        assert_eq!(builtins.get("_DATE"), Some(Value::from(year.to_string())));
+
+        assert!(Builtins::new(&dst, None, Some("%Q")).is_err());
+    }
+
+    #[test]
+    fn git_user() {
+        let repo = temp_dir().join("makeit-builtins-git");
+        create_dir_all(repo.join(".git")).unwrap();
+        write(
+            repo.join(".git").join("config"),
+            "[core]\n\tname = core\n[user]\n\tname = \"Jane Doe\"\n\
+             \temail = jane@doe.com ; comment\n",
+        )
+        .unwrap();
+
+        let builtins = Builtins::new(&repo.join("sub"), None, None).unwrap();
+        assert_eq!(builtins.get("_GIT_NAME"), Some(Value::from("Jane Doe")));
+        assert_eq!(
+            builtins.get("_GIT_EMAIL"),
+            Some(Value::from("jane@doe.com"))
+        );
+    }
+
+    #[test]
+    fn lazy_vars() {
+        let dst = temp_dir().join("makeit-builtins-lazy");
+        let builtins = Rc::new(Builtins::new(&dst, None, None).unwrap());
+        let opts = ParserOptions {
+            builtins: Some(builtins.clone()),
+            ..Default::default()
+        };
+        let mut vars = HashMap::new();
+        vars.insert("_USER".to_string(), Value::from("me"));
+
+        let mut input = "{{ _OS }} {{ _USER }}".chars().map(Ok);
+        let mut result = String::new();
+        Parser::string(&mut input, &vars, &mut result, &opts).unwrap();
+        assert_eq!(result, format!("{} me", env::consts::OS));
+
+        assert!(builtins.is_computed("_OS"));
+        assert!(!builtins.is_computed("_USER"));
+        assert!(!builtins.is_computed("_GIT_NAME"));
+        assert!(!builtins.is_computed("_DATE"));
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{read_to_string, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    use makeit::{
+        args::Args,
+        bundled::{install, names, state, InstallState},
+        config::Config,
+        hooks::Hooks,
+    };
+
+    #[test]
+    fn install_builtin() {
+        let dir = temp_dir().join("makeit-bundled");
+        _ = remove_dir_all(&dir);
+        let config = Config {
+            template_dir: dir.clone(),
+            ..Default::default()
+        };
+
+        let names = names();
+        for name in ["c", "latex", "react", "react-native", "readme", "rust"] {
+            assert!(names.contains(&name));
+        }
+
+        let mut args = Args {
+            template: Some("c".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(state(&config, "c").unwrap(), InstallState::Missing);
+        install(&config, &args).unwrap();
+        assert_eq!(state(&config, "c").unwrap(), InstallState::UpToDate);
+        assert!(dir.join("c").join("template").join(".gitignore").exists());
+
+        write(dir.join("c").join("template").join("Makefile"), "").unwrap();
+        install(&config, &args).unwrap();
+        assert_eq!(
+            state(&config, "c").unwrap(),
+            InstallState::Differs(vec![PathBuf::from("template/Makefile")])
+        );
+
+        args.force = true;
+        install(&config, &args).unwrap();
+        assert_eq!(state(&config, "c").unwrap(), InstallState::UpToDate);
+
+        assert!(state(&config, "missing").is_err());
+    }
+
+    #[test]
+    fn builtin_hooks() {
+        let dir = temp_dir().join("makeit-bundled-hooks");
+        _ = remove_dir_all(&dir);
+        let config = Config {
+            template_dir: dir.clone(),
+            ..Default::default()
+        };
+        install(&config, &Args::default()).unwrap();
+
+        for name in names() {
+            let path = dir.join(name).join("makeit.json");
+            let manifest: serde_json::Value =
+                serde_json::from_str(&read_to_string(path).unwrap()).unwrap();
+            for hook in ["pre", "post"] {
+                let Some(hooks) = manifest.get(hook) else {
+                    continue;
+                };
+                let hooks: Hooks =
+                    serde_json::from_value(hooks.clone()).unwrap();
+                for step in hooks.0 {
+                    assert!(
+                        step.shell || !step.command.contains("&&"),
+                        "{name} {hook} uses shell operator without shell"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
+
+    #[test]
+    fn comments_discarded() {
+        let mut input = "{{# Entry point of the project #}}
+main:{{# spans
+multiple }} lines #}} {{ a }}
+{{# trims #-}}
+end"
+        .chars()
+        .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), "hello".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                drop_block_lines: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, "main: hello\nend");
+    }
+}
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use std::{
+    env::temp_dir,
+    fs::{create_dir_all, remove_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use makeit::{
+    args::Args, config::Config, err::error::Error, template::Template,
+    vars::Vars,
+};
+
+/// Gets temporary directory for the test with given name, its previous
+/// contents are removed
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = temp_dir().join(format!("makeit-{name}"));
+    _ = remove_dir_all(&dir);
+    dir
+}
+
+/// Creates config with the config file and the template directory in `dir`,
+/// so the tests don't touch the user config (such as trusted templates)
+pub fn config(dir: &Path) -> Config {
+    Config {
+        path: dir.join("config.json"),
+        template_dir: dir.join("templates"),
+        ..Default::default()
+    }
+}
+
+/// Creates args, which answer yes in the prompts
+pub fn yes() -> Args {
+    Args {
+        yes: true,
+        ..Default::default()
+    }
+}
+
+/// Loads template with given name to the `project` directory in `dir`, using
+/// config from [`config`] and other options from `args`
+pub fn load(dir: &Path, name: &str, args: Args) -> Result<(), Error> {
+    let args = Args {
+        template: Some(name.to_string()),
+        dst: Some(dir.join("project").to_string_lossy().to_string()),
+        ..args
+    };
+    Template::load(&config(dir), &args, Vars::default())
+}
+
+/// Creates template with given manifest and files in `dir`
+pub fn template(
+    dir: &Path,
+    name: &str,
+    manifest: &str,
+    files: &[(&str, &str)],
+) {
+    let tmplt = dir.join(name);
+    create_dir_all(tmplt.join("template")).unwrap();
+    write(tmplt.join("makeit.json"), manifest).unwrap();
+    for (file, content) in files {
+        write(tmplt.join("template").join(file), content).unwrap();
+    }
+}
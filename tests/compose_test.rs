@@ -0,0 +1,73 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use makeit::{
+        args::Args,
+        err::{error::Error, template_err::TemplateErr},
+    };
+
+    use crate::common::{load, template, test_dir, yes};
+
+    #[test]
+    fn multiple_templates() {
+        let dir = test_dir("compose");
+        let tmplts = dir.join("templates");
+
+        let make = r#""fileOptions": { "README.md": { "action": "Make" } }"#;
+        template(
+            &tmplts,
+            "rust",
+            r#"{ "vars": { "name": "app", "lang": "rust" },
+                 "computed": { "title": "name + \" (\" + lang + \")\"" } }"#,
+            &[("main.rs", "fn main() {}")],
+        );
+        template(
+            &tmplts,
+            "readme",
+            &format!(r#"{{ "vars": {{ "name": "readme" }}, {make} }}"#),
+            &[("README.md", "# {{ title }} {{ name }}")],
+        );
+        template(
+            &tmplts,
+            "ci",
+            r#"{ "dependencies": ["rust"] }"#,
+            &[("ci.yml", "ci")],
+        );
+
+        let args = Args {
+            extra_templates: vec!["readme".to_string()],
+            ..yes()
+        };
+        load(&dir, "ci", args).unwrap();
+
+        let dst = dir.join("project");
+
+        assert_eq!(
+            read_to_string(dst.join("main.rs")).unwrap(),
+            "fn main() {}"
+        );
+        assert_eq!(read_to_string(dst.join("ci.yml")).unwrap(), "ci");
+        assert_eq!(
+            read_to_string(dst.join("README.md")).unwrap(),
+            "# readme (rust) readme"
+        );
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let dir = test_dir("compose-cycle");
+        let tmplts = dir.join("templates");
+        template(&tmplts, "a", r#"{ "dependencies": ["b"] }"#, &[]);
+        template(&tmplts, "b", r#"{ "dependencies": ["a"] }"#, &[]);
+
+        let res = load(&dir, "a", yes());
+        let Err(Error::TemplateErr(TemplateErr::DependencyCycle(c))) = res
+        else {
+            panic!("expected dependency cycle");
+        };
+        assert_eq!(c, "a -> b -> a");
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+        path::Path,
+    };
+
+    use makeit::{
+        config::{Config, TemplateSource, PROJECT_TEMPLATES},
+        err::config_err::ConfigErr,
+    };
+
+    #[test]
+    fn load_errors() {
+        let dir = temp_dir().join("makeit-config-load");
+        create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.json");
+        assert!(matches!(
+            Config::load(Some(&missing)),
+            Err(ConfigErr::NotFound(_))
+        ));
+
+        let invalid = dir.join("invalid.json");
+        write(&invalid, "{\n    \"templateDir\": \"/tmp\",\n    vars\n}")
+            .unwrap();
+        let err = Config::load(Some(&invalid)).unwrap_err();
+        assert!(matches!(err, ConfigErr::Invalid(..)));
+        assert!(err.to_string().contains("line 3"));
+
+        let valid = dir.join("valid.json");
+        write(&valid, r#"{ "templateDir": "/tmp/templates" }"#).unwrap();
+        let config = Config::load(Some(&valid)).unwrap();
+        assert_eq!(config.template_dir, Path::new("/tmp/templates"));
+        assert_eq!(config.path, valid);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"templateDir":"/tmp/templates"}"#);
+
+        let old = dir.join("old.json");
+        write(&old, r#"{ "template_dir": "/tmp/old" }"#).unwrap();
+        let config = Config::load(Some(&old)).unwrap();
+        assert_eq!(config.template_dir, Path::new("/tmp/old"));
+    }
+
+    #[test]
+    fn template_dirs() {
+        let root = temp_dir().join("makeit-config-dirs");
+        _ = remove_dir_all(&root);
+
+        let project = root.join("project");
+        let env_dir = root.join("env");
+        let personal = root.join("personal");
+        let shared = root.join("shared");
+        create_dir_all(project.join(PROJECT_TEMPLATES).join("rust")).unwrap();
+        create_dir_all(project.join("src")).unwrap();
+        create_dir_all(env_dir.join("ci")).unwrap();
+        create_dir_all(personal.join("rust")).unwrap();
+        create_dir_all(shared.join("rust")).unwrap();
+        create_dir_all(shared.join("latex")).unwrap();
+
+        let config = Config {
+            template_dir: personal.clone(),
+            template_dirs: vec![shared.clone(), personal.clone()],
+            ..Default::default()
+        };
+
+        let dirs = config.template_dirs_in(
+            Some(&project.join("src")),
+            Some(env_dir.as_os_str()),
+        );
+        let sources: Vec<_> = dirs.iter().map(|d| d.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                TemplateSource::Project,
+                TemplateSource::Env,
+                TemplateSource::Config,
+                TemplateSource::Config,
+            ]
+        );
+        assert_eq!(dirs[2].path, personal);
+        assert_eq!(dirs[3].path, shared);
+
+        assert_eq!(dirs[0].path, project.join(PROJECT_TEMPLATES));
+        assert_eq!(dirs[1].path, env_dir);
+
+        let dirs = config.template_dirs_in(Some(&root), None);
+        let sources: Vec<_> = dirs.iter().map(|d| d.source).collect();
+        assert_eq!(sources, vec![TemplateSource::Config; 2]);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{
+        delims::Delims, parser::Parser, parser_options::ParserOptions,
+    };
+
+    #[test]
+    fn custom_delims() {
+        let mut input = "<% a + \" \" + b %> {{ a }}
+\\<% a %>
+[<% a == \"hello\" ? \"yes\" : \"no\" %>]"
+            .chars()
+            .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), "hello".into());
+        vars.insert("b".to_string(), "world".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                delims: Delims::new("<%", "%>"),
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, "hello world {{ a }}\n<% a %>\n[yes]");
+    }
+
+    #[test]
+    fn bracket_delims() {
+        let mut input = "[[a]]] [[ b ?? \"none\" ]]".chars().map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), "hello".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                delims: Delims::new("[[", "]]"),
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, "hello] none");
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
+
+    #[test]
+    fn env_func() {
+        let opts = ParserOptions {
+            env: Some(HashMap::from([(
+                "MAKEIT_TEST_EDITOR".to_string(),
+                "vim".to_string(),
+            )])),
+            ..Default::default()
+        };
+
+        let mut input = "{{ env(\"MAKEIT_TEST_EDITOR\") }} \
+{{ env(\"MAKEIT_TEST_MISSING\") ?? \"none\" }} \
+{{ \"ed: \" + env(\"MAKEIT_TEST_EDITOR\") }}"
+            .chars()
+            .map(Ok);
+        let vars = HashMap::new();
+
+        let mut result = String::new();
+        Parser::string(&mut input, &vars, &mut result, &opts).unwrap();
+        assert_eq!(result, "vim none ed: vim");
+    }
+
+    #[test]
+    fn env_allowed() {
+        let opts = ParserOptions {
+            allowed_env: Some(vec!["MAKEIT_TEST_C*".to_string()]),
+            env: Some(HashMap::from([(
+                "MAKEIT_TEST_CI".to_string(),
+                "true".to_string(),
+            )])),
+            ..Default::default()
+        };
+        let vars = HashMap::new();
+
+        let mut input = "{{ env(\"MAKEIT_TEST_CI\") }}".chars().map(Ok);
+        let mut result = String::new();
+        Parser::string(&mut input, &vars, &mut result, &opts).unwrap();
+        assert_eq!(result, "true");
+
+        let mut input = "{{ env(\"MAKEIT_TEST_EDITOR\") }}".chars().map(Ok);
+        let mut result = String::new();
+        assert!(Parser::string(&mut input, &vars, &mut result, &opts).is_err());
+
+        let mut input = "{{ home(\"x\") }}".chars().map(Ok);
+        let mut result = String::new();
+        assert!(Parser::string(&mut input, &vars, &mut result, &opts).is_err());
+    }
+}
@@ -2,7 +2,7 @@
 mod tests {
     use std::collections::HashMap;
 
-    use makeit::parse::parser::Parser;
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
 
     #[test]
     fn string_equals() {
@@ -13,7 +13,12 @@ mod tests {
         let vars = HashMap::new();
 
         let mut result = String::new();
-        _ = Parser::string(&mut input, &vars, &mut result);
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
         assert_eq!(result, "true\nfalse");
     }
 
@@ -27,12 +32,17 @@ mod tests {
             .chars()
             .map(Ok);
         let mut vars = HashMap::new();
-        vars.insert("a".to_string(), "hello".to_string());
-        vars.insert("b".to_string(), "hello".to_string());
-        vars.insert("c".to_string(), "test".to_string());
+        vars.insert("a".to_string(), "hello".into());
+        vars.insert("b".to_string(), "hello".into());
+        vars.insert("c".to_string(), "test".into());
 
         let mut result = String::new();
-        _ = Parser::string(&mut input, &vars, &mut result);
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
         assert_eq!(result, "true\nfalse\ntrue\nfalse\ntrue");
     }
 }
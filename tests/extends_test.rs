@@ -0,0 +1,82 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use makeit::err::{error::Error, template_err::TemplateErr};
+
+    use crate::common::{load, template, test_dir, yes};
+
+    #[test]
+    fn extends() {
+        let dir = test_dir("extends");
+        let tmplts = dir.join("templates");
+        template(
+            &tmplts,
+            "c",
+            r#"{
+                "vars": { "name": "app", "cc": "gcc" },
+                "fileOptions": {
+                    "Makefile": { "action": "Make" },
+                    "main.c": { "action": "Make" }
+                }
+            }"#,
+            &[
+                ("Makefile", "{{ cc }} {{ name }}"),
+                ("main.c", "// {{ name }}"),
+                ("util.c", "util"),
+            ],
+        );
+        template(
+            &tmplts,
+            "c-lib",
+            r#"{
+                "extends": "c",
+                "vars": { "name": "lib" },
+                "fileOptions": {
+                    "Makefile": { "action": "Make", "name": "makefile" },
+                    "util.c": { "action": "Ignore" }
+                }
+            }"#,
+            &[
+                ("Makefile", "{{ cc }} -shared {{ name }}"),
+                ("lib.c", "lib"),
+            ],
+        );
+
+        load(&dir, "c-lib", yes()).unwrap();
+
+        let project = dir.join("project");
+        assert_eq!(
+            read_to_string(project.join("makefile")).unwrap(),
+            "gcc -shared lib"
+        );
+        assert!(!project.join("Makefile").exists());
+        assert_eq!(read_to_string(project.join("main.c")).unwrap(), "// lib");
+        assert_eq!(read_to_string(project.join("lib.c")).unwrap(), "lib");
+        assert!(!project.join("util.c").exists());
+    }
+
+    #[test]
+    fn extends_cycle() {
+        let dir = test_dir("extends-cycle");
+        let tmplts = dir.join("templates");
+        template(&tmplts, "a", r#"{ "extends": "b" }"#, &[]);
+        template(&tmplts, "b", r#"{ "extends": "c" }"#, &[]);
+        template(&tmplts, "c", r#"{ "extends": "a" }"#, &[]);
+
+        let Err(Error::TemplateErr(TemplateErr::ExtendsCycle(c))) =
+            load(&dir, "a", yes())
+        else {
+            panic!("expected inheritance cycle");
+        };
+        assert_eq!(c, "a -> b -> c -> a");
+
+        template(&tmplts, "d", r#"{ "extends": "missing" }"#, &[]);
+        assert!(matches!(
+            load(&dir, "d", yes()),
+            Err(Error::TemplateErr(TemplateErr::NotFound(_)))
+        ));
+    }
+}
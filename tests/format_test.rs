@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::Path};
+
+    use makeit::{format::Format, parse::ast::Value};
+
+    #[test]
+    fn format_lookup() {
+        assert_eq!(Format::from_path(Path::new("a.toml")), Format::Toml);
+        assert_eq!(Format::from_path(Path::new("a.yml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("a")), Format::Json);
+        assert_eq!("yaml".parse::<Format>().unwrap(), Format::Yaml);
+        assert!("xml".parse::<Format>().is_err());
+
+        let manifests: Vec<_> =
+            Format::ALL.iter().map(|f| f.manifest()).collect();
+        assert_eq!(manifests, ["makeit.json", "makeit.toml", "makeit.yaml"]);
+    }
+
+    #[test]
+    fn format_round_trip() {
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), Value::from("app"));
+        vars.insert("port".to_string(), Value::Number(8080.0));
+        vars.insert("ratio".to_string(), Value::Number(1.5));
+        vars.insert("lib".to_string(), Value::Bool(true));
+
+        for format in Format::ALL {
+            let text = format.serialize(&vars).unwrap();
+            assert!(text.contains("8080") && !text.contains("8080.0"));
+
+            let res: HashMap<String, Value> =
+                format.deserialize(&text).unwrap();
+            assert_eq!(res, vars);
+        }
+    }
+}
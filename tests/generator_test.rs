@@ -0,0 +1,140 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{create_dir_all, read_to_string, write},
+        io::Write,
+        path::Path,
+        process::{Command, Stdio},
+    };
+
+    use makeit::{
+        args::Args,
+        err::{error::Error, template_err::TemplateErr},
+    };
+
+    use crate::common::{load, test_dir, yes};
+
+    fn run(dir: &Path, gen: &str, gen_args: &[&str]) -> Result<(), Error> {
+        let args = Args {
+            gen_args: gen_args.iter().map(|a| a.to_string()).collect(),
+            ..yes()
+        };
+        load(dir, gen, args)
+    }
+
+    #[test]
+    fn generator() {
+        let dir = test_dir("generator");
+        let react = dir.join("templates").join("react");
+        create_dir_all(react.join("template")).unwrap();
+        write(react.join("makeit.json"), "{}").unwrap();
+
+        let gen = react.join("generators").join("component");
+        create_dir_all(gen.join("template")).unwrap();
+        write(
+            gen.join("makeit.json"),
+            r#"{
+                "args": ["name"],
+                "target": "src/components/{{ name }}",
+                "vars": { "style": "css" },
+                "fileOptions": {
+                    "index.tsx": { "action": "Make" },
+                    "style": { "action": "Make", "name": "{{ name }}.{{ style }}" }
+                }
+            }"#,
+        )
+        .unwrap();
+        write(gen.join("template").join("index.tsx"), "// {{ name }}")
+            .unwrap();
+        write(gen.join("template").join("style"), ".{{ name }} {}").unwrap();
+
+        let app = dir.join("project");
+        create_dir_all(app.join("src")).unwrap();
+        write(app.join("src").join("main.tsx"), "main").unwrap();
+
+        run(&dir, "react:component", &["Button"]).unwrap();
+
+        let comp = app.join("src/components/Button");
+        assert_eq!(
+            read_to_string(comp.join("index.tsx")).unwrap(),
+            "// Button"
+        );
+        assert_eq!(
+            read_to_string(comp.join("Button.css")).unwrap(),
+            ".Button {}"
+        );
+        assert_eq!(read_to_string(app.join("src/main.tsx")).unwrap(), "main");
+
+        assert!(matches!(
+            run(&dir, "react:component", &["A", "B"]),
+            Err(Error::TemplateErr(TemplateErr::TooManyArgs(_, 1)))
+        ));
+        assert!(matches!(
+            run(&dir, "react:hook", &[]),
+            Err(Error::TemplateErr(TemplateErr::NotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn overwrite_prompt() {
+        let dir = test_dir("generator-overwrite");
+        let gen = dir.join("templates/react/generators/component");
+        create_dir_all(gen.join("template")).unwrap();
+        write(dir.join("templates/react/makeit.json"), "{}").unwrap();
+        write(
+            gen.join("makeit.json"),
+            r#"{
+                "args": ["name"],
+                "target": "{{ name }}",
+                "fileOptions": {
+                    "style": { "action": "Make", "name": "{{ name }}.css" },
+                    "notes": { "action": "Ignore" }
+                }
+            }"#,
+        )
+        .unwrap();
+        write(gen.join("template").join("style"), ".{{ name }} {}").unwrap();
+        write(gen.join("template").join("notes"), "notes").unwrap();
+        let config = dir.join("config.json");
+        write(
+            &config,
+            format!(r#"{{ "templateDir": {:?} }}"#, dir.join("templates")),
+        )
+        .unwrap();
+
+        let run = |name: &str, input: &str| {
+            let mut child = Command::new(env!("CARGO_BIN_EXE_makeit"))
+                .args(["react:component", name, "-d", "app", "--config"])
+                .arg(&config)
+                .current_dir(&dir)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .spawn()
+                .unwrap();
+            let mut stdin = child.stdin.take().unwrap();
+            stdin.write_all(input.as_bytes()).unwrap();
+            drop(stdin);
+            assert!(child.wait().unwrap().success());
+        };
+
+        let button = dir.join("app/Button");
+        create_dir_all(&button).unwrap();
+        write(button.join("Button.css"), "old").unwrap();
+        run("Button", "n\n");
+        assert_eq!(read_to_string(button.join("Button.css")).unwrap(), "old");
+        run("Button", "y\n");
+        assert_eq!(
+            read_to_string(button.join("Button.css")).unwrap(),
+            ".Button {}"
+        );
+
+        let card = dir.join("app/Card");
+        create_dir_all(&card).unwrap();
+        write(card.join("notes"), "mine").unwrap();
+        run("Card", "");
+        assert_eq!(read_to_string(card.join("Card.css")).unwrap(), ".Card {}");
+        assert_eq!(read_to_string(card.join("notes")).unwrap(), "mine");
+    }
+}
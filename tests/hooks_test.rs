@@ -0,0 +1,107 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{create_dir_all, read_to_string, write},
+        path::Path,
+        process::Command,
+    };
+
+    use makeit::{
+        args::Args,
+        err::{error::Error, hook_err::HookErr},
+        format::Format,
+        hooks::{run_command, Hooks, Step},
+    };
+
+    use crate::common::{self, test_dir, yes};
+
+    fn load(dir: &Path, manifest: &str) -> Result<(), Error> {
+        let tmplt = dir.join("templates").join("app");
+        create_dir_all(tmplt.join("template").join("sub")).unwrap();
+        write(tmplt.join("makeit.json"), manifest).unwrap();
+
+        let args = Args {
+            trust_scripts: true,
+            ..yes()
+        };
+        common::load(dir, "app", args)
+    }
+
+    #[test]
+    fn hook_steps() {
+        let dir = test_dir("hooks");
+        load(
+            &dir,
+            r#"{
+                "vars": { "name": "app", "git": false },
+                "pre": "touch pre",
+                "post": [
+                    "touch {{ name }}",
+                    { "command": "echo hi > shell && echo x", "shell": true },
+                    { "command": "touch git", "when": "git" },
+                    { "command": "false", "continueOnError": true },
+                    { "command": "touch in-sub", "cwd": "sub" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let project = dir.join("project");
+        assert!(project.join("pre").exists());
+        assert!(project.join("app").exists());
+        assert_eq!(read_to_string(project.join("shell")).unwrap(), "hi\n");
+        assert!(!project.join("git").exists());
+        assert!(project.join("sub").join("in-sub").exists());
+    }
+
+    #[test]
+    fn hook_failure() {
+        let dir = test_dir("hooks-fail");
+        let res = load(
+            &dir,
+            r#"{ "post": ["true", "ls missing-file", "touch never"] }"#,
+        );
+        let Err(Error::HookErr(e)) = res else {
+            panic!("expected failed hook step");
+        };
+        let msg = e.to_string();
+        let HookErr::Failed(step, cmd, code, tail) = e else {
+            panic!("expected failed hook step");
+        };
+        assert_eq!(step, "post hook step 2");
+        assert_eq!(cmd, "ls missing-file");
+        assert_eq!(code, Some(2));
+        assert!(tail.contains("missing-file"));
+        assert!(msg.starts_with(
+            "post hook step 2: 'ls missing-file' failed with exit code 2\n  "
+        ));
+        assert!(!dir.join("project").join("never").exists());
+    }
+
+    #[test]
+    fn hook_repr() {
+        let hooks: Hooks = Format::Json
+            .deserialize(r#"["a", { "command": "b", "shell": true }]"#)
+            .unwrap();
+        assert_eq!(hooks.0[0], Step::from("a".to_string()));
+        assert!(hooks.0[1].shell);
+
+        let single = Hooks::from(Some("a".to_string()));
+        assert_eq!(Format::Json.serialize(&single).unwrap(), "\"a\"");
+    }
+
+    #[test]
+    fn stderr_tail() {
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            "for i in $(seq 1 20); do echo $i >&2; done; exit 3",
+        ]);
+        let (status, tail) = run_command(&mut command, true).unwrap();
+        assert_eq!(status.code(), Some(3));
+        let expected: Vec<_> = (11..=20).map(|i| i.to_string()).collect();
+        assert_eq!(tail, expected.join("\n"));
+    }
+}
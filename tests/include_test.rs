@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        env::temp_dir,
+        fs::{create_dir_all, write},
+        path::PathBuf,
+    };
+
+    use makeit::{
+        err::{error::Error, include_err::IncludeErr},
+        parse::{parser::Parser, parser_options::ParserOptions},
+    };
+
+    fn setup(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = temp_dir().join(format!("makeit-include-{name}"));
+        for (file, content) in files {
+            let path = dir.join(file);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn include_files() {
+        let dir = setup(
+            "files",
+            &[
+                ("root/header.txt", "// {{ name }} by {{ include \"a\" }}"),
+                ("partials/a", "{{ author }}"),
+                ("partials/header.txt", "shadowed"),
+            ],
+        );
+        let mut input = "{{ include \"header.txt\" }}
+{{ include \"a\" + \"\" -}}
+!"
+        .chars()
+        .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), "test".into());
+        vars.insert("author".to_string(), "Martan03".into());
+
+        let mut result = String::new();
+        let res = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                include_dirs: vec![dir.join("root"), dir.join("partials")],
+                ..Default::default()
+            },
+        );
+        assert!(res.is_ok());
+        assert_eq!(result, "// test by Martan03\nMartan03!");
+    }
+
+    #[test]
+    fn include_cycle() {
+        let dir = setup(
+            "cycle",
+            &[("a", "{{ include \"b\" }}"), ("b", "{{ include \"a\" }}")],
+        );
+        let mut input = "{{ include \"a\" }}".chars().map(Ok);
+        let vars = HashMap::new();
+
+        let mut result = String::new();
+        let res = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                include_dirs: vec![dir],
+                ..Default::default()
+            },
+        );
+        match res {
+            Err(Error::IncludeErr(IncludeErr::Cycle(chain))) => {
+                let names: Vec<_> = chain
+                    .iter()
+                    .map(|p| p.file_name().unwrap().to_string_lossy())
+                    .collect();
+                assert_eq!(names, vec!["a", "b", "a"]);
+            }
+            _ => panic!("expected include cycle"),
+        }
+    }
+
+    #[test]
+    fn include_not_found() {
+        let dir = setup("missing", &[("a", "{{ include \"missing\" }}")]);
+        let mut input = "{{ include \"a\" }}".chars().map(Ok);
+        let vars = HashMap::new();
+
+        let mut result = String::new();
+        let res = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                include_dirs: vec![dir],
+                ..Default::default()
+            },
+        );
+        match res {
+            Err(Error::IncludeErr(IncludeErr::NotFound(name, chain))) => {
+                assert_eq!(name, "missing");
+                assert_eq!(chain.len(), 1);
+            }
+            _ => panic!("expected include not found"),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, read_to_string, write};
+
+    use makeit::{
+        args::Args, config::Config, err::error::Error,
+        err::template_err::TemplateErr, template::Template,
+    };
+
+    use crate::common::{config, test_dir};
+
+    fn args(src: &str, dst: &str) -> Args {
+        Args {
+            template: Some(src.to_string()),
+            target: Some(dst.to_string()),
+            yes: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rename_copy() {
+        let dir = test_dir("manage");
+        let config = config(&dir);
+        let tmplts = &config.template_dir;
+        create_dir_all(tmplts.join("app").join("template")).unwrap();
+        write(tmplts.join("app").join("makeit.json"), "{}").unwrap();
+
+        Template::copy(&config, &args("app", "lib")).unwrap();
+        assert!(tmplts.join("app").join("makeit.json").exists());
+        assert!(tmplts.join("lib").join("template").is_dir());
+
+        Template::rename(&config, &args("lib", "bin")).unwrap();
+        assert!(!tmplts.join("lib").exists());
+        assert_eq!(
+            read_to_string(tmplts.join("bin/makeit.json")).unwrap(),
+            "{}"
+        );
+
+        assert!(matches!(
+            Template::rename(&config, &args("lib", "other")),
+            Err(Error::TemplateErr(TemplateErr::NotFound(_)))
+        ));
+        assert!(matches!(
+            Template::copy(&config, &args("app", "app")),
+            Err(Error::TemplateErr(TemplateErr::Exists(_)))
+        ));
+        assert!(matches!(
+            Template::copy(&config, &args("app", "../app")),
+            Err(Error::TemplateErr(TemplateErr::InvalidName(_)))
+        ));
+
+        write(tmplts.join("app").join("makeit.json"), "{ }").unwrap();
+        Template::copy(&config, &args("app", "bin")).unwrap();
+        assert_eq!(
+            read_to_string(tmplts.join("bin/makeit.json")).unwrap(),
+            "{ }"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn same_dir() {
+        let dir = test_dir("manage-same");
+        let config = config(&dir);
+        let tmplts = &config.template_dir;
+        create_dir_all(tmplts.join("app").join("template")).unwrap();
+        write(tmplts.join("app").join("makeit.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(tmplts.join("app"), tmplts.join("alias"))
+            .unwrap();
+
+        assert!(matches!(
+            Template::copy(&config, &args("app", "alias")),
+            Err(Error::TemplateErr(TemplateErr::Exists(_)))
+        ));
+        _ = Template::rename(&config, &args("app", "alias"));
+        assert!(tmplts.join("alias").join("makeit.json").exists());
+    }
+
+    #[test]
+    fn create_update() {
+        let dir = test_dir("manage-update");
+        let config = config(&dir);
+        let manifest = r#"{
+    "fileOptions": {
+        "main.c": { "action": "Make" },
+        "old.c": { "action": "Ignore" }
+    },
+    "vars": { "name": "app" }
+}"#;
+        let tmplt = config.template_dir.join("app");
+        create_dir_all(tmplt.join("template")).unwrap();
+        write(tmplt.join("makeit.json"), manifest).unwrap();
+        write(tmplt.join("template").join("old.c"), "old").unwrap();
+
+        let project = dir.join("project");
+        create_dir_all(&project).unwrap();
+        write(project.join("main.c"), "main").unwrap();
+
+        let args = Args {
+            template: Some("app".to_string()),
+            dst: Some(project.to_string_lossy().to_string()),
+            update: true,
+            yes: true,
+            ..Default::default()
+        };
+        Template::create(&config, args).unwrap();
+
+        assert_eq!(
+            read_to_string(tmplt.join("makeit.json")).unwrap(),
+            manifest
+        );
+        assert_eq!(
+            read_to_string(tmplt.join("template/main.c")).unwrap(),
+            "main"
+        );
+        assert!(!tmplt.join("template/old.c").exists());
+
+        let loaded = Template::read_manifest(&tmplt).unwrap().unwrap();
+        assert_eq!(loaded.stale_options(&tmplt.join("template")), ["old.c"]);
+
+        let args = Args {
+            template: Some("missing".to_string()),
+            dst: Some(project.to_string_lossy().to_string()),
+            update: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Template::create(&config, args),
+            Err(Error::TemplateErr(TemplateErr::NotFound(_)))
+        ));
+
+        let config = Config {
+            template_dirs: vec![dir.join("extra")],
+            ..config
+        };
+        let extra = dir.join("extra").join("lib");
+        create_dir_all(extra.join("template")).unwrap();
+        write(extra.join("makeit.json"), "{}").unwrap();
+        let args = Args {
+            template: Some("lib".to_string()),
+            dst: Some(project.to_string_lossy().to_string()),
+            update: true,
+            yes: true,
+            ..Default::default()
+        };
+        Template::create(&config, args).unwrap();
+        assert!(extra.join("template/main.c").exists());
+        assert!(!config.template_dir.join("lib").exists());
+    }
+}
@@ -2,7 +2,7 @@
 mod tests {
     use std::collections::HashMap;
 
-    use makeit::parse::parser::Parser;
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
 
     #[test]
     fn null_check_tests() {
@@ -15,12 +15,17 @@ mod tests {
             .chars()
             .map(Ok);
         let mut vars = HashMap::new();
-        vars.insert("a".to_string(), "a not null".to_string());
-        vars.insert("b".to_string(), "behave".to_string());
-        vars.insert("c".to_string(), "test".to_string());
+        vars.insert("a".to_string(), "a not null".into());
+        vars.insert("b".to_string(), "behave".into());
+        vars.insert("c".to_string(), "test".into());
 
         let mut result = String::new();
-        _ = Parser::string(&mut input, &vars, &mut result);
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
         assert_eq!(
             result,
             "a not null\nbehave\ntest null\ntest\nbehave\na not null"
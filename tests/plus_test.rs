@@ -2,7 +2,7 @@
 mod tests {
     use std::collections::HashMap;
 
-    use makeit::parse::parser::Parser;
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
 
     #[test]
     fn plus_test() {
@@ -11,11 +11,16 @@ mod tests {
             .chars()
             .map(Ok);
         let mut vars = HashMap::new();
-        vars.insert("a".to_string(), "hello".to_string());
-        vars.insert("b".to_string(), "world".to_string());
+        vars.insert("a".to_string(), "hello".into());
+        vars.insert("b".to_string(), "world".into());
 
         let mut result = String::new();
-        _ = Parser::string(&mut input, &vars, &mut result);
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
         assert_eq!(result, "true\nthis world crazy");
     }
 }
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{
+        ast::Value, parser::Parser, parser_options::ParserOptions,
+    };
+
+    #[test]
+    fn set_vars() {
+        let mut input = "{{ name ?? \"none\" }}
+{{ set name = name ?? _PNAME ?? \"main\" -}}
+{{ name }}.c
+{{ let name = \"lib\" + name }}{{ name }}
+{{ set name = missing }}{{ name ?? \"unset\" }}"
+            .chars()
+            .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("_PNAME".to_string(), "proj".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
+        assert_eq!(result, "none\nproj.c\nlibproj\nunset");
+    }
+
+    #[test]
+    fn set_invalid() {
+        let mut input = "{{ set = \"a\" }}".chars().map(Ok);
+        let vars = HashMap::new();
+
+        let mut result = String::new();
+        let res = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn eval_expr() {
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), "hello".into());
+        let opts = ParserOptions::default();
+
+        let mut input = "a + \" world\"".chars().map(Ok);
+        let res = Parser::eval(&mut input, &vars, &opts).unwrap();
+        assert_eq!(res, Value::String("hello world".to_string()));
+
+        let mut input = "b".chars().map(Ok);
+        let res = Parser::eval(&mut input, &vars, &opts).unwrap();
+        assert_eq!(res, Value::Null);
+
+        let mut input = "a b".chars().map(Ok);
+        assert!(Parser::eval(&mut input, &vars, &opts).is_err());
+    }
+}
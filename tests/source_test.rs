@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, read_to_string, remove_dir_all, write},
+        path::Path,
+        process::Command,
+    };
+
+    use makeit::{
+        args::Args,
+        config::Config,
+        source::{add, upgrade, Source, SourceKind, SOURCE_FILE},
+    };
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@test"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success());
+    }
+
+    #[test]
+    fn add_git() {
+        let dir = temp_dir().join("makeit-source-git");
+        _ = remove_dir_all(&dir);
+        let work = dir.join("work");
+        create_dir_all(work.join("template")).unwrap();
+        write(work.join("makeit.json"), "{}").unwrap();
+        write(work.join("template").join("main.c"), "v1").unwrap();
+        git(&work, &["init", "--quiet"]);
+        git(&work, &["add", "-A"]);
+        git(&work, &["commit", "--quiet", "-m", "v1"]);
+        git(&dir, &["clone", "--quiet", "--bare", "work", "app.git"]);
+
+        let config = Config {
+            template_dir: dir.join("templates"),
+            ..Default::default()
+        };
+        let url = format!("file://{}", dir.join("app.git").display());
+        let args = Args {
+            source: Some(url.clone()),
+            ..Default::default()
+        };
+        add(&config, &args).unwrap();
+
+        let tmplt = config.template_dir.join("app");
+        assert_eq!(
+            read_to_string(tmplt.join("template/main.c")).unwrap(),
+            "v1"
+        );
+        assert!(!tmplt.join(".git").exists());
+        let source = Source::load(&tmplt).unwrap().unwrap();
+        assert_eq!(source.kind, SourceKind::Git);
+        assert_eq!(source.location, url);
+        let rev = source.revision.unwrap();
+
+        write(work.join("template").join("main.c"), "v2").unwrap();
+        git(&work, &["commit", "--quiet", "-am", "v2"]);
+        git(&work, &["push", "--quiet", "../app.git", "HEAD"]);
+
+        upgrade(&config, &Args::default()).unwrap();
+        assert_eq!(
+            read_to_string(tmplt.join("template/main.c")).unwrap(),
+            "v2"
+        );
+        let source = Source::load(&tmplt).unwrap().unwrap();
+        assert_ne!(source.revision.unwrap(), rev);
+    }
+
+    #[test]
+    fn add_dir() {
+        let dir = temp_dir().join("makeit-source-dir");
+        _ = remove_dir_all(&dir);
+        let src = dir.join("collection").join("rust");
+        create_dir_all(src.join("template")).unwrap();
+        write(src.join("makeit.json"), "{}").unwrap();
+        write(src.join("template").join("Cargo.toml"), "v1").unwrap();
+
+        let config = Config {
+            template_dir: dir.join("templates"),
+            ..Default::default()
+        };
+        let args = Args {
+            template: Some("rust".to_string()),
+            source: Some(dir.join("collection").display().to_string()),
+            ..Default::default()
+        };
+        add(&config, &args).unwrap();
+
+        let tmplt = config.template_dir.join("rust");
+        let source = Source::load(&tmplt).unwrap().unwrap();
+        assert_eq!(source.kind, SourceKind::Path);
+        assert_eq!(source.subdir.as_deref(), Some("rust"));
+        assert_eq!(source.revision, None);
+
+        write(src.join("template").join("Cargo.toml"), "v2").unwrap();
+        let args = Args {
+            template: Some("rust".to_string()),
+            ..Default::default()
+        };
+        upgrade(&config, &args).unwrap();
+        assert_eq!(
+            read_to_string(tmplt.join("template/Cargo.toml")).unwrap(),
+            "v2"
+        );
+
+        let args = Args {
+            source: Some(dir.join("missing").display().to_string()),
+            ..Default::default()
+        };
+        assert!(add(&config, &args).is_err());
+
+        let args = Args {
+            template: Some("../rust".to_string()),
+            source: Some(dir.join("collection").display().to_string()),
+            yes: true,
+            ..Default::default()
+        };
+        assert!(add(&config, &args).is_err());
+        assert!(!dir.join("rust").exists());
+    }
+
+    #[test]
+    fn invalid_source() {
+        let dir = temp_dir().join("makeit-source-invalid");
+        _ = remove_dir_all(&dir);
+        let config = Config {
+            template_dir: dir.join("templates"),
+            ..Default::default()
+        };
+        let tmplt = config.template_dir.join("app");
+        create_dir_all(tmplt.join("template")).unwrap();
+        write(tmplt.join("makeit.json"), "{}").unwrap();
+
+        let args = Args {
+            template: Some("app".to_string()),
+            ..Default::default()
+        };
+        let sources = [
+            r#"{ "kind": "git", "location": "--upload-pack=touch pwned" }"#,
+            r#"{ "kind": "path", "location": "file:///tmp" }"#,
+            r#"{ "kind": "path", "location": "/tmp", "subdir": "../.." }"#,
+        ];
+        for source in sources {
+            write(tmplt.join(SOURCE_FILE), source).unwrap();
+            assert!(Source::load(&tmplt).is_err());
+            assert!(upgrade(&config, &args).is_err());
+        }
+        assert!(!dir.join("pwned").exists());
+        assert!(tmplt.join("makeit.json").exists());
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{parser::Parser, parser_options::ParserOptions};
+
+    #[test]
+    fn trim_markers() {
+        let mut input = "items:
+    {{- a }}, {{ b -}}
+    ;
+{{ a -}}   \\{{ b }}
+end"
+        .chars()
+        .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), "hello".into());
+        vars.insert("b".to_string(), "world".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
+        assert_eq!(result, "items:hello, world;\nhello{{ b }}\nend");
+    }
+
+    #[test]
+    fn drop_block_lines() {
+        let mut input = "[package]
+{{ lib ? \"[lib]\" : \"\" }}
+name = \"{{ name }}\"
+    {{ bin ? \"\" : \"\" }}  {{ lib ? \"\" : \"\" }}
+
+{{ \"\" }} end
+{{ lib ? \"\" : \"\" }}"
+            .chars()
+            .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), "test".into());
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions {
+                drop_block_lines: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, "[package]\nname = \"test\"\n\n end\n");
+    }
+
+    #[test]
+    fn keep_block_lines() {
+        let mut input = "a\n{{ b ? \"b\" : \"\" }}\nc".chars().map(Ok);
+        let vars = HashMap::new();
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
+        assert_eq!(result, "a\n\nc");
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    use makeit::{
+        args::Args,
+        err::{error::Error, template_err::TemplateErr},
+        trust::{manifest_hash, TrustStore},
+    };
+
+    use crate::common::{config, load, test_dir, yes};
+
+    #[test]
+    fn trusted_hooks() {
+        let dir = test_dir("trust");
+        let tmplt = dir.join("templates").join("app");
+        create_dir_all(tmplt.join("template")).unwrap();
+        write(tmplt.join("makeit.json"), r#"{ "post": "touch post" }"#)
+            .unwrap();
+        let config = config(&dir);
+
+        let mut store = TrustStore::load(&config).unwrap();
+        let hash = manifest_hash(std::slice::from_ref(&tmplt)).unwrap();
+        assert!(!store.is_trusted("app", &hash));
+
+        assert!(matches!(
+            load(&dir, "app", yes()),
+            Err(Error::TemplateErr(TemplateErr::UntrustedHooks(_)))
+        ));
+        assert!(!dir.join("project").exists());
+
+        let trusted = Args {
+            trust_scripts: true,
+            ..yes()
+        };
+        load(&dir, "app", trusted).unwrap();
+        assert!(dir.join("project").join("post").exists());
+        assert!(!TrustStore::load(&config).unwrap().is_trusted("app", &hash));
+        remove_dir_all(dir.join("project")).unwrap();
+
+        store.trust("app", hash.clone());
+        store.save().unwrap();
+
+        let store = TrustStore::load(&config).unwrap();
+        assert!(store.is_trusted("app", &hash));
+        assert!(!store.is_trusted("other", &hash));
+
+        let no_scripts = Args {
+            no_scripts: true,
+            ..Default::default()
+        };
+        load(&dir, "app", no_scripts).unwrap();
+        assert!(!dir.join("project").join("post").exists());
+
+        load(&dir, "app", Args::default()).unwrap();
+        assert!(dir.join("project").join("post").exists());
+
+        write(tmplt.join("makeit.json"), r#"{ "post": "touch other" }"#)
+            .unwrap();
+        assert_ne!(manifest_hash(&[tmplt]).unwrap(), hash);
+    }
+}
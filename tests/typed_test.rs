@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use makeit::parse::{
+        ast::Value, parser::Parser, parser_options::ParserOptions,
+    };
+
+    #[test]
+    fn typed_vars() {
+        let mut input = "{{ ts ? \"ts\" : \"js\" }}
+{{ lib ? \"lib\" : \"bin\" }}
+{{ port + 1 }} {{ port + \"1\" }}
+{{ list }} {{ list + list }}
+{{ lib == false }} {{ port == 8080 }} {{ port == \"8080\" }}
+{{ missing == null }} {{ 1.5 + 2 }}"
+            .chars()
+            .map(Ok);
+        let mut vars = HashMap::new();
+        vars.insert("ts".to_string(), Value::Bool(true));
+        vars.insert("lib".to_string(), Value::Bool(false));
+        vars.insert("port".to_string(), Value::Number(8080.0));
+        vars.insert(
+            "list".to_string(),
+            Value::List(vec!["a".into(), "b".into()]),
+        );
+
+        let mut result = String::new();
+        _ = Parser::string(
+            &mut input,
+            &vars,
+            &mut result,
+            &ParserOptions::default(),
+        );
+        assert_eq!(
+            result,
+            "ts\nbin\n8081 80801\na, b a, b, a, b\ntrue true false\ntrue 3.5"
+        );
+    }
+
+    #[test]
+    fn compat_values() {
+        assert_eq!(Value::Bool(false).to_compat(), Some("false".into()));
+        assert_eq!(Value::Number(2.0).to_compat(), Some("2".into()));
+        assert_eq!(Value::from("a").to_compat(), Some("a".into()));
+        assert_eq!(Value::Null.to_compat(), None);
+    }
+
+    #[test]
+    fn infer_values() {
+        assert_eq!(Value::infer("true"), Value::Bool(true));
+        assert_eq!(Value::infer("false"), Value::Bool(false));
+        assert_eq!(Value::infer("8080"), Value::Number(8080.0));
+        assert_eq!(Value::infer("-1.5"), Value::Number(-1.5));
+        assert_eq!(Value::infer("1.50"), Value::from("1.50"));
+        assert_eq!(Value::infer("007"), Value::from("007"));
+        assert_eq!(Value::infer("inf"), Value::from("inf"));
+        assert_eq!(Value::infer("False"), Value::from("False"));
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::write};
+
+    use makeit::{
+        parse::ast::Value,
+        vars::{VarLayer, Vars},
+    };
+
+    #[test]
+    fn layer_precedence() {
+        let mut vars = Vars::default();
+        vars.insert(VarLayer::Args, "name", "args".to_string());
+        vars.insert(VarLayer::Template, "name", "template".to_string());
+        vars.insert(VarLayer::Template, "author", "template".to_string());
+        vars.insert(VarLayer::Builtin, "_PNAME", "builtin".to_string());
+        vars.insert(VarLayer::Env, "_PNAME", "env".to_string());
+
+        let res = vars.resolve();
+        assert_eq!(res.len(), 3);
+        assert_eq!(res["name"], Value::from("args"));
+        assert_eq!(res["author"], Value::from("template"));
+        assert_eq!(res["_PNAME"], Value::from("env"));
+
+        let sources = vars.sources();
+        let layers: Vec<_> = sources.values().map(|(_, l)| *l).collect();
+        assert_eq!(
+            layers,
+            vec![VarLayer::Env, VarLayer::Template, VarLayer::Args]
+        );
+    }
+
+    #[test]
+    fn answers_file() {
+        let files = [
+            (
+                "answers.json",
+                r#"{ "name": "app", "ts": true, "lib": false, "port": 80 }"#,
+            ),
+            (
+                "answers.toml",
+                "name = \"app\"\nts = true\nlib = false\nport = 80",
+            ),
+            ("answers.yaml", "name: app\nts: true\nlib: false\nport: 80"),
+        ];
+
+        for (file, content) in files {
+            let path = temp_dir().join(format!("makeit-{file}"));
+            write(&path, content).unwrap();
+
+            let vars = Vars::file(&path).unwrap();
+            assert_eq!(vars.len(), 4);
+            assert_eq!(vars["name"], Value::from("app"));
+            assert_eq!(vars["ts"], Value::Bool(true));
+            assert_eq!(vars["lib"], Value::Bool(false));
+            assert_eq!(vars["port"], Value::Number(80.0));
+        }
+    }
+}